@@ -1,4 +1,9 @@
-use std::{cell::RefCell, collections::HashMap, fmt::Display, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    rc::Rc,
+};
 
 use crate::object::Object;
 
@@ -6,6 +11,8 @@ use crate::object::Object;
 pub struct Environment {
     store: HashMap<String, Object>,
     outer_env: Option<Rc<RefCell<Environment>>>,
+    /// names visible to files importing this one
+    exports: HashSet<String>,
 }
 
 impl Default for Environment {
@@ -19,6 +26,7 @@ impl Environment {
         Self {
             store: HashMap::new(),
             outer_env: None,
+            exports: HashSet::new(),
         }
     }
 
@@ -26,6 +34,7 @@ impl Environment {
         Self {
             store: HashMap::new(),
             outer_env: Some(outer_env),
+            exports: HashSet::new(),
         }
     }
 
@@ -33,6 +42,43 @@ impl Environment {
         self.store.insert(name, value);
     }
 
+    /// Updates the binding in the scope that defined it.
+    /// Returns false if the name was never declared
+    pub fn update(&mut self, name: &str, value: Object) -> bool {
+        if let Some(obj) = self.store.get_mut(name) {
+            *obj = value;
+            return true;
+        }
+
+        match &self.outer_env {
+            Some(outer_env) => outer_env.borrow_mut().update(name, value),
+            None => false,
+        }
+    }
+
+    pub fn export(&mut self, name: String) {
+        self.exports.insert(name);
+    }
+
+    /// Exported names along with their current values
+    pub fn exported(&self) -> HashMap<String, Object> {
+        self.exports
+            .iter()
+            .filter_map(|name| self.get(name).map(|val| (name.clone(), val)))
+            .collect()
+    }
+
+    /// Every name visible from this scope
+    pub fn names(&self) -> HashSet<String> {
+        let mut names = match &self.outer_env {
+            Some(outer_env) => outer_env.borrow().names(),
+            None => HashSet::new(),
+        };
+        names.extend(self.store.keys().cloned());
+
+        names
+    }
+
     pub fn get(&self, name: &str) -> Option<Object> {
         match self.store.get(name) {
             Some(obj) => Some(obj.clone()),
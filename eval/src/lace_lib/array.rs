@@ -4,7 +4,7 @@ pub fn append(obj: Object, appendable: Object) -> Object {
     let mut arr = match obj {
         Object::Array(arr) => arr,
         _ => {
-            return Object::Error(format!("Expected an array, Got {}", obj.kind()));
+            return Object::error(format!("Expected an array, Got {}", obj.kind()));
         }
     };
 
@@ -0,0 +1,23 @@
+use crate::object::Object;
+
+pub fn keys(obj: Object) -> Object {
+    if let Object::HashLiteral(hmap) = obj {
+        Object::Array(hmap.into_keys().collect())
+    } else {
+        Object::error(format!(
+            "{} does not have any associated function keys()",
+            obj.kind()
+        ))
+    }
+}
+
+pub fn values(obj: Object) -> Object {
+    if let Object::HashLiteral(hmap) = obj {
+        Object::Array(hmap.into_values().collect())
+    } else {
+        Object::error(format!(
+            "{} does not have any associated function values()",
+            obj.kind()
+        ))
+    }
+}
@@ -1,3 +1,5 @@
 pub mod array;
+pub mod hashmap;
+pub mod num;
 pub mod std;
 pub mod str;
@@ -0,0 +1,64 @@
+use crate::object::Object;
+
+/// Floats outside of this range can't be turned into integers
+const I64_BOUND: f64 = 9_223_372_036_854_775_808.0;
+
+/// Integer part of a float, failing when there is no such integer
+fn truncate(f: f64) -> Object {
+    if f.is_finite() && (-I64_BOUND..I64_BOUND).contains(&f) {
+        Object::Integer(f.trunc() as i64)
+    } else {
+        Object::error(format!("{} doesn't fit in a 64 bit integer", f))
+    }
+}
+
+/// Converts to an integer. Floats are truncated towards zero
+pub fn int(obj: Object) -> Object {
+    match obj {
+        Object::Integer(i) => Object::Integer(i),
+        Object::Float(f) => truncate(f),
+        Object::Boolean(b) => Object::Integer(b as i64),
+        Object::Str(s) => match s.trim().parse::<i64>() {
+            Ok(i) => Object::Integer(i),
+            Err(_) => Object::error(format!("Cannot convert \"{}\" to an integer", s)),
+        },
+        _ => Object::error(format!("Cannot convert {} to an integer", obj.kind())),
+    }
+}
+
+pub fn float(obj: Object) -> Object {
+    match obj {
+        Object::Integer(i) => Object::Float(i as f64),
+        Object::Float(f) => Object::Float(f),
+        Object::Str(s) => match s.trim().parse::<f64>() {
+            Ok(f) => Object::Float(f),
+            Err(_) => Object::error(format!("Cannot convert \"{}\" to a float", s)),
+        },
+        _ => Object::error(format!("Cannot convert {} to a float", obj.kind())),
+    }
+}
+
+/// Nearest integer, halfway cases are rounded away from zero
+pub fn round(obj: Object) -> Object {
+    rounded(obj, f64::round, "round")
+}
+
+pub fn floor(obj: Object) -> Object {
+    rounded(obj, f64::floor, "floor")
+}
+
+pub fn ceil(obj: Object) -> Object {
+    rounded(obj, f64::ceil, "ceil")
+}
+
+fn rounded(obj: Object, op: fn(f64) -> f64, name: &str) -> Object {
+    match obj {
+        Object::Integer(i) => Object::Integer(i),
+        Object::Float(f) => truncate(op(f)),
+        _ => Object::error(format!(
+            "{} does not have any associated function {}()",
+            obj.kind(),
+            name
+        )),
+    }
+}
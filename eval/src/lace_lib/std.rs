@@ -15,7 +15,7 @@ pub fn read(obj: Object) -> Object {
     io::stdout().flush().unwrap();
     let mut buffer = String::new();
     if io::stdin().read_line(&mut buffer).is_err() {
-        return Object::Error("Failed to read from stdin".into());
+        return Object::error("Failed to read from stdin");
     };
 
     match obj {
@@ -34,11 +34,11 @@ pub fn read(obj: Object) -> Object {
             if let 1 = buffer.len() {
                 Object::Char(buffer.chars().next().unwrap())
             } else {
-                Object::Error("More than one characters received".into())
+                Object::error("More than one characters received")
             }
         }
         Object::Str(_) => Object::Str(buffer),
-        _ => Object::Error(format!("No associated function read() for {}", obj.kind())),
+        _ => Object::error(format!("No associated function read() for {}", obj.kind())),
     }
 }
 
@@ -47,7 +47,7 @@ pub fn len(obj: Object) -> Object {
         Object::Str(s) => s.len(),
         Object::Array(arr) => arr.len(),
         _ => {
-            return Object::Error(format!(
+            return Object::error(format!(
                 "{} does not have any associated function len()",
                 obj.kind()
             ));
@@ -71,7 +71,7 @@ pub fn strip_start(obj: Object, strip: Object) -> Object {
                 let mdf = &s[(i as usize)..];
                 Object::Str(mdf.into())
             }
-            _ => Object::Error(format!("{} cannot be stripped from a String", obj.kind())),
+            _ => Object::error(format!("{} cannot be stripped from a String", obj.kind())),
         },
         Object::Array(arr) => match strip {
             Object::Array(stripable) => {
@@ -87,10 +87,10 @@ pub fn strip_start(obj: Object, strip: Object) -> Object {
                 let mdf = &arr[(i as usize)..];
                 Object::Array(mdf.into())
             }
-            _ => Object::Error(format!("{} cannot be stripped from an Array", obj.kind())),
+            _ => Object::error(format!("{} cannot be stripped from an Array", obj.kind())),
         },
 
-        _ => Object::Error(format!(
+        _ => Object::error(format!(
             "No associated function strip_start() for {}",
             obj.kind()
         )),
@@ -111,7 +111,7 @@ pub fn strip_end(obj: Object, strip: Object) -> Object {
                 let mdf = &s[..(s.len() - i as usize)];
                 Object::Str(mdf.into())
             }
-            _ => Object::Error(format!("{} cannot be stripped from a ", obj.kind())),
+            _ => Object::error(format!("{} cannot be stripped from a ", obj.kind())),
         },
         Object::Array(arr) => match strip {
             Object::Array(stripable) => {
@@ -128,9 +128,9 @@ pub fn strip_end(obj: Object, strip: Object) -> Object {
                 let mdf = &arr[..(i as usize)];
                 Object::Array(mdf.into())
             }
-            _ => Object::Error(format!("{} cannot be stripped from an Array", obj.kind())),
+            _ => Object::error(format!("{} cannot be stripped from an Array", obj.kind())),
         },
-        _ => Object::Error(format!(
+        _ => Object::error(format!(
             "No associated function strip_start() for {}",
             obj.kind()
         )),
@@ -145,11 +145,11 @@ pub fn first(obj: Object) -> Object {
         },
         Object::Array(a) => {
             if a.is_empty() {
-                return Object::Error("Array is empty".into());
+                return Object::error("Array is empty");
             }
             a[0].clone()
         }
-        _ => Object::Error(format!(
+        _ => Object::error(format!(
             "{} does not have any associated function first()",
             obj.kind()
         )),
@@ -165,11 +165,11 @@ pub fn last(obj: Object) -> Object {
         Object::Array(a) => {
             let arr_len = a.len();
             if arr_len == 0 {
-                return Object::Error("Array is empty".into());
+                return Object::error("Array is empty");
             }
             a[arr_len - 1].clone()
         }
-        _ => Object::Error(format!(
+        _ => Object::error(format!(
             "{} does not have any associated function last()",
             obj.kind()
         )),
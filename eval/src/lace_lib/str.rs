@@ -6,7 +6,7 @@ pub fn split(obj: Object, delimeter: Object) -> Object {
             Object::Str(s) => s,
             Object::Char(c) => c.to_string(),
             _ => {
-                return Object::Error(format!(
+                return Object::error(format!(
                     "Expected String or character, got {}",
                     delimeter.kind()
                 ));
@@ -15,7 +15,7 @@ pub fn split(obj: Object, delimeter: Object) -> Object {
         let split_str = s.split(&delim).map(|sec| Object::Str(sec.into())).collect();
         Object::Array(split_str)
     } else {
-        Object::Error(format!(
+        Object::error(format!(
             "{} does not have any associated function first()",
             obj.kind()
         ))
@@ -27,7 +27,7 @@ pub fn chars(obj: Object) -> Object {
         let chs = s.chars().map(Object::Char).collect();
         Object::Array(chs)
     } else {
-        Object::Error(format!(
+        Object::error(format!(
             "{} does not have any associated function chars()",
             obj.kind()
         ))
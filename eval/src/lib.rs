@@ -1,26 +1,37 @@
 pub mod environment;
 pub mod lace_lib;
+pub mod loader;
 pub mod object;
 
-use std::{self, cell::RefCell, collections::HashMap, fs, rc::Rc};
+#[cfg(test)]
+mod tests;
 
-use crate::{environment::Environment, object::Object};
-use lace_lexer::{
-    token::{kind::TokenKind, Token},
-    Lexer,
-};
+use std::{self, cell::RefCell, cmp::Ordering, collections::HashMap, path::PathBuf, rc::Rc};
+
+use crate::{environment::Environment, loader::Loader, object::Object};
+use lace_lexer::token::{kind::TokenKind, span::Span, Token};
 use lace_parser::{
     ast::{
-        nodes::{ConditionalOperator, HashLiteral, IdentNode, IndexAccess, PrimitiveNode},
-        statement::{BlockStatement, Statement},
+        nodes::{
+            Assignment, ConditionalOperator, ForLoop, FunctionCall, HashLiteral, IdentNode,
+            IndexAccess, MatchExpression, MemberAccess, PrimitiveNode, WhileLoop,
+        },
+        pattern::Pattern,
+        statement::{BlockStatement, ImportStatement, SourcePath, SourceStatement, Statement},
         Expression, Program,
     },
-    Parser,
+    diagnostic::closest,
+};
+use object::{
+    builtin::BuiltinFunction,
+    error::{Frame, RuntimeError},
+    function::Function,
+    module::Module,
 };
-use object::{builtin::BuiltinFunction, function::Function};
 
 pub struct Eval {
     environment: Rc<RefCell<Environment>>,
+    pub loader: Loader,
 }
 
 impl Default for Eval {
@@ -33,8 +44,18 @@ impl Eval {
     pub fn new() -> Self {
         Self {
             environment: Rc::new(RefCell::new(Environment::new())),
+            loader: Loader::new(),
         }
     }
+
+    /// Evaluator for a program read from a file.
+    /// Files it sources are looked for relative to it
+    pub fn for_file(file: PathBuf) -> Self {
+        let mut eval = Self::new();
+        eval.loader.enter(file.canonicalize().unwrap_or(file));
+
+        eval
+    }
 }
 
 impl Eval {
@@ -42,19 +63,29 @@ impl Eval {
         let mut obj = Object::Null;
 
         for statement in program.statements {
-            obj = self.eval_statement(statement);
+            obj = match self.eval_statement(statement) {
+                Object::Break | Object::Continue => Self::stray_loop_control(),
+                obj => obj,
+            };
+
+            if obj.errored() {
+                return obj;
+            }
         }
 
         obj
     }
 
+    fn stray_loop_control() -> Object {
+        Object::error("break and continue can only be used inside a loop")
+    }
+
     fn eval_block(&mut self, block: BlockStatement) -> Object {
         let mut res = Object::Null;
         for statement in block.statements {
             res = self.eval_statement(statement);
-            match res {
-                Object::Error(_) | Object::Return(_) => return res,
-                _ => (),
+            if res.interrupts() {
+                return res;
             }
         }
 
@@ -81,76 +112,171 @@ impl Eval {
             }
             Statement::Expression(expr) => self.eval_expression(expr),
             Statement::Source(sourceable) => {
-                let mut fpath = std::env::current_dir()
-                    .unwrap()
-                    .to_str()
-                    .expect("") // TODO provide some insight into what went wrong
-                    .to_owned();
-                fpath.push('/');
-                fpath.push_str(sourceable.path.to_str().unwrap());
-                fpath.push_str(".lace");
-                let code = match fs::read_to_string(fpath.clone()) {
-                    Ok(code) => code,
-                    Err(err) => {
-                        println!("{:?}", fpath);
-                        eprintln!("{err}");
-                        std::process::exit(1);
-                    }
+                let path = match self.source_path(sourceable) {
+                    Ok(path) => path,
+                    Err(err) => return err,
+                };
+
+                let (file, program) = match self.loader.load(&path) {
+                    Ok(loaded) => loaded,
+                    Err(err) => return err,
+                };
+
+                self.loader.enter(file);
+                let res = self.eval(program);
+                self.loader.exit();
+
+                res
+            }
+            Statement::Import(import) => self.eval_import(import),
+            Statement::Export(st) => {
+                let name = st.name.label.clone();
+                let res = self.eval_statement(Statement::Assignment(st));
+                if !res.errored() {
+                    self.environment.borrow_mut().export(name);
+                }
+                res
+            }
+            Statement::Break(_) => Object::Break,
+            Statement::Continue(_) => Object::Continue,
+        }
+    }
+
+    /// Evaluates the file in a scope of its own, once,
+    /// and binds whatever it exports to the alias
+    fn eval_import(&mut self, import: ImportStatement) -> Object {
+        let path = match self.source_path(import.source) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let module = match self.loader.cached_module(&path) {
+            Some(module) => module,
+            None => {
+                let (file, program) = match self.loader.load(&path) {
+                    Ok(loaded) => loaded,
+                    Err(err) => return err,
                 };
 
-                let lexer = Lexer::new(code);
-                let mut parser = Parser::new(lexer);
-                let program = parser.parse_program();
-                if !parser.errors.is_empty() {
-                    parser.errors.iter().for_each(|e| {
-                        println!("{}", e.emit_err());
-                    })
+                let module_env = Rc::new(RefCell::new(Environment::new()));
+                let curr_env = std::mem::replace(&mut self.environment, Rc::clone(&module_env));
+                self.loader.enter(file.clone());
+                let res = self.eval(program);
+                self.loader.exit();
+                self.environment = curr_env;
+
+                if res.errored() {
+                    return res;
                 }
-                self.eval(program)
+
+                let name = match file.file_stem() {
+                    Some(stem) => stem.to_string_lossy().to_string(),
+                    None => file.display().to_string(),
+                };
+                let module = Object::Module(Module {
+                    name,
+                    members: module_env.borrow().exported(),
+                });
+                self.loader.cache_module(file, module.clone());
+
+                module
+            }
+        };
+
+        self.environment
+            .borrow_mut()
+            .upsert(import.alias.label, module);
+
+        Object::Null
+    }
+
+    /// Path of the file to source, evaluating it if it isn't a literal
+    fn source_path(&mut self, source: SourceStatement) -> Result<PathBuf, Object> {
+        let expr = match source.path {
+            SourcePath::Literal(path) => return Ok(path),
+            SourcePath::Computed(expr) => expr,
+        };
+
+        let span = expr.span();
+        match self.eval_expression(expr) {
+            Object::Str(path) => Ok(PathBuf::from(path)),
+            Object::Error(err) => Err(Object::Error(err)),
+            obj => {
+                let mut err = RuntimeError::from(format!(
+                    "Expected a path to source, received {}",
+                    obj.kind()
+                ))
+                .with_code("E0103");
+                err.span = Some(span);
+                err.file = self.loader.current().cloned();
+
+                Err(Object::Error(Box::new(err)))
             }
         }
     }
 
+    /// Errors which don't know where they occured yet
+    /// are pinned to the expression that produced them
     fn eval_expression(&mut self, expression: Expression) -> Object {
+        let span = expression.span();
+
+        match self.eval_expression_kind(expression) {
+            Object::Error(mut err) if err.span.is_none() => {
+                err.span = Some(span);
+                err.file = self.loader.current().cloned();
+                Object::Error(err)
+            }
+            obj => obj,
+        }
+    }
+
+    fn eval_expression_kind(&mut self, expression: Expression) -> Object {
         match expression {
             Expression::Identifier(ident) => self.eval_ident(ident),
-            Expression::Primitive(primitive) => Self::eval_primitive(primitive),
+            Expression::Primitive(literal) => Self::eval_primitive(literal.val),
             Expression::Unary(prefix) => {
                 let right = self.eval_expression(*prefix.right_expr);
+                if right.errored() {
+                    return right;
+                }
+
                 Self::eval_prefix(&prefix.operator, &right)
             }
             Expression::Binary(infix) => {
-                let (left, right) = (
-                    self.eval_expression(*infix.left_expr),
-                    self.eval_expression(*infix.right_expr),
-                );
+                let left = self.eval_expression(*infix.left_expr);
+                if left.errored() {
+                    return left;
+                }
 
-                Self::eval_infix(&infix.operator, left, right)
-            }
-            Expression::Conditional(conditional) => self.eval_conditional(conditional),
-            Expression::FunctionDef(func) => {
-                let params = func.params;
-                let body = func.body;
-
-                Object::Function(Function {
-                    params,
-                    body,
-                    environment: Rc::clone(&self.environment),
-                })
-            }
-            Expression::FunctionCall(fn_call) => {
-                let function = self.eval_expression(*fn_call.function);
-                if function.errored() {
-                    return function;
+                // the right operand is only evaluated when it decides the result
+                let decided = match infix.operator.kind {
+                    TokenKind::And => !left.truthy(),
+                    TokenKind::Or => left.truthy(),
+                    _ => false,
+                };
+                if decided {
+                    return Object::Boolean(left.truthy());
+                }
+                if infix.operator.kind == TokenKind::NullCoalesce && left != Object::Null {
+                    return left;
                 }
 
-                let args = self.eval_expressions(fn_call.args);
-                if args.len() == 1 && args[0].errored() {
-                    return args[0].clone();
+                let right = self.eval_expression(*infix.right_expr);
+                if right.errored() {
+                    return right;
                 }
 
-                self.apply_func(function, args)
+                Self::eval_infix(&infix.operator, left, right)
             }
+            Expression::Conditional(conditional) => self.eval_conditional(conditional),
+            Expression::FunctionDef(func) => Object::Function(Function {
+                name: func.name,
+                params: func.params,
+                body: Rc::new(func.body),
+                environment: Rc::clone(&self.environment),
+                file: self.loader.current().cloned(),
+            }),
+            Expression::FunctionCall(fn_call) => self.eval_call(fn_call).unwrap_or(Object::Null),
             Expression::Array(arr) => {
                 let elements = self.eval_expressions(arr.elements);
                 if elements.len() == 1 && elements[0].errored() {
@@ -159,20 +285,55 @@ impl Eval {
                     Object::Array(elements)
                 }
             }
-            Expression::ArrIndex(index_access) => self.eval_index_expr(index_access),
+            Expression::ArrIndex(index_access) => {
+                self.eval_index_expr(index_access).unwrap_or(Object::Null)
+            }
             Expression::HashMapLiteral(hmap) => self.eval_hashmap_expr(hmap),
+            Expression::While(while_loop) => self.eval_while(while_loop),
+            Expression::For(for_loop) => self.eval_for(for_loop),
+            Expression::Assign(assignment) => self.eval_assignment(assignment),
+            Expression::Member(member) => self.eval_member(member).unwrap_or(Object::Null),
+            Expression::Match(match_expr) => self.eval_match(match_expr),
+        }
+    }
+
+    /// Frame for a call made from the current file
+    fn frame(&self, label: &str, call_site: Option<Span>) -> Frame {
+        Frame {
+            name: label.into(),
+            span: call_site,
+            file: self.loader.current().cloned(),
         }
     }
 
-    fn apply_func(&mut self, function: Object, args: Vec<Object>) -> Object {
+    /// Applies the function to the arguments.
+    /// Errors raised inside user functions record the call in their trace
+    fn apply_func(&mut self, function: Object, args: Vec<Object>, call: Frame) -> Object {
         match function {
             Object::Function(func) => {
                 let extended_env = Self::extended_func_env(&func, args);
                 let curr_env = Rc::clone(&self.environment);
                 self.environment = Rc::new(RefCell::new(extended_env));
-                let eval_body = self.eval_block(func.body);
+                if let Some(file) = &func.file {
+                    self.loader.enter(file.clone());
+                }
+                let eval_body = self.eval_block((*func.body).clone());
+                if func.file.is_some() {
+                    self.loader.exit();
+                }
                 self.environment = curr_env;
-                eval_body
+                match eval_body {
+                    Object::Return(val) => *val,
+                    Object::Break | Object::Continue => Self::stray_loop_control(),
+                    Object::Error(mut err) => {
+                        err.trace.push(Frame {
+                            name: func.name.unwrap_or(call.name),
+                            ..call
+                        });
+                        Object::Error(err)
+                    }
+                    _ => eval_body,
+                }
             }
             Object::Builtin(bfunc) => {
                 let returned = bfunc.apply(args.clone());
@@ -183,7 +344,7 @@ impl Eval {
                         let var_name = match args[0].clone() {
                             Object::Str(label) => label,
                             _ => {
-                                return Object::Error("Couldn't find variable".into());
+                                return Object::error("Couldn't find variable");
                             }
                         };
                         self.environment.borrow_mut().upsert(var_name, returned);
@@ -192,7 +353,130 @@ impl Eval {
                 }
                 returned
             }
-            _ => Object::Error(format!("{} not found", function)),
+            _ => Object::error(format!("{} not found", function)),
+        }
+    }
+
+    /// Evaluates a link of a member, index or call chain like `a?.b[0].c()`.
+    /// None when an optional link found null, which makes the whole chain null
+    fn eval_chain(&mut self, expr: Expression) -> Option<Object> {
+        match expr {
+            Expression::ArrIndex(index_access) => self.eval_index_expr(index_access),
+            Expression::Member(member) => self.eval_member(member),
+            Expression::FunctionCall(fn_call) => self.eval_call(fn_call),
+            _ => Some(self.eval_expression(expr)),
+        }
+    }
+
+    fn eval_call(&mut self, fn_call: FunctionCall) -> Option<Object> {
+        let call_site = Some(fn_call.span.clone());
+
+        let function = match *fn_call.function {
+            Expression::Member(member) => {
+                return self.eval_method_call(member, fn_call.args, call_site)
+            }
+            function => function,
+        };
+
+        let label = match &function {
+            Expression::Identifier(ident) => ident.label.clone(),
+            _ => "<anonymous>".into(),
+        };
+
+        let function = self.eval_chain(function)?;
+        if function.errored() {
+            return Some(function);
+        }
+
+        let args = self.eval_expressions(fn_call.args);
+        if args.len() == 1 && args[0].errored() {
+            return Some(args[0].clone());
+        }
+
+        let call = self.frame(&label, call_site);
+        Some(self.apply_func(function, args, call))
+    }
+
+    /// Calls a function stored in a hashmap field,
+    /// or a builtin with the receiver as its first argument
+    fn eval_method_call(
+        &mut self,
+        member: MemberAccess,
+        args: Vec<Expression>,
+        call_site: Option<Span>,
+    ) -> Option<Object> {
+        let receiver = self.eval_chain(*member.object)?;
+        if receiver.errored() {
+            return Some(receiver);
+        }
+        if member.optional && receiver == Object::Null {
+            return None;
+        }
+
+        let mut args = self.eval_expressions(args);
+        if args.len() == 1 && args[0].errored() {
+            return Some(args[0].clone());
+        }
+
+        let label = member.member.label;
+        let call = self.frame(&label, call_site);
+        match &receiver {
+            Object::HashLiteral(hmap) => {
+                if let Some(field) = hmap.get(&Object::Str(label.clone())) {
+                    return Some(self.apply_func(field.clone(), args, call));
+                }
+            }
+            Object::Module(_) => {
+                let function = Self::module_member(&receiver, &label);
+                if function.errored() {
+                    return Some(function);
+                }
+                return Some(self.apply_func(function, args, call));
+            }
+            _ => (),
+        }
+
+        let res = match BuiltinFunction::try_builtin(&label) {
+            Some(method) => {
+                args.insert(0, receiver);
+                self.apply_func(method, args, call)
+            }
+            None => Object::error(format!("{} has no method {}", receiver.kind(), label)),
+        };
+
+        Some(res)
+    }
+
+    fn eval_member(&mut self, member: MemberAccess) -> Option<Object> {
+        let object = self.eval_chain(*member.object)?;
+        if object.errored() {
+            return Some(object);
+        }
+        if member.optional && object == Object::Null {
+            return None;
+        }
+
+        let label = member.member.label;
+        let res = match &object {
+            Object::HashLiteral(hmap) => match hmap.get(&Object::Str(label.clone())) {
+                Some(field) => field.clone(),
+                None if member.optional => Object::Null,
+                None => Object::error(format!("HashMap has no field {}", label)),
+            },
+            Object::Module(_) => Self::module_member(&object, &label),
+            _ => Object::error(format!("{} has no field {}", object.kind(), label)),
+        };
+
+        Some(res)
+    }
+
+    fn module_member(module: &Object, label: &str) -> Object {
+        match module {
+            Object::Module(module) => match module.members.get(label) {
+                Some(member) => member.clone(),
+                None => Object::error(format!("Module {} does not export {}", module.name, label)),
+            },
+            _ => Object::error(format!("{} is not a module", module.kind())),
         }
     }
 
@@ -205,6 +489,9 @@ impl Eval {
         env
     }
 
+    // keys are restricted to primitives by `check_hashable`, so
+    // the interior mutability of functions never reaches a key
+    #[allow(clippy::mutable_key_type)]
     fn eval_hashmap_expr(&mut self, h_pairs: HashLiteral) -> Object {
         let mut hmap = HashMap::new();
 
@@ -214,11 +501,8 @@ impl Eval {
                 return key;
             }
 
-            if !matches!(
-                key,
-                Object::Integer(_) | Object::Char(_) | Object::Str(_) | Object::Boolean(_)
-            ) {
-                return Object::Error(format!("Cannot hash a {}", key.kind()));
+            if let Some(err) = Self::check_hashable(&key) {
+                return err;
             }
 
             let val = self.eval_expression(val);
@@ -232,35 +516,35 @@ impl Eval {
         Object::HashLiteral(hmap)
     }
 
-    fn eval_index_expr(&mut self, index_expr: IndexAccess) -> Object {
-        let collection = self.eval_expression(*index_expr.arr);
+    fn eval_index_expr(&mut self, index_expr: IndexAccess) -> Option<Object> {
+        let collection = self.eval_chain(*index_expr.arr)?;
         if collection.errored() {
-            return collection;
+            return Some(collection);
+        }
+        if index_expr.optional && collection == Object::Null {
+            return None;
         }
 
         let index = self.eval_expression(*index_expr.index);
         if index.errored() {
-            return index;
-        }
-
-        match (&collection, &index) {
-            (Object::Array(a), Object::Integer(i)) => {
-                let l = a.len();
-                if *i < 0 {
-                    return Object::Error("Negative indexing isn't valid".into());
-                } else if *i >= l as i64 {
-                    return Object::Error(format!(
-                        "Index {} out of bounds for an array of length {}",
-                        i, l
-                    ));
-                }
-                a[*i as usize].clone()
-            }
-            (Object::HashLiteral(h), _) => match h.get(&index) {
+            return Some(index);
+        }
+
+        Some(Self::index_into(&collection, &index, index_expr.optional))
+    }
+
+    fn index_into(collection: &Object, index: &Object, optional: bool) -> Object {
+        match (collection, index) {
+            (Object::Array(a), Object::Integer(i)) => match Self::checked_index(a.len(), *i) {
+                Ok(i) => a[i].clone(),
+                Err(_) if optional => Object::Null,
+                Err(err) => err,
+            },
+            (Object::HashLiteral(h), _) => match h.get(index) {
                 Some(h) => h.clone(),
                 None => Object::Null,
             },
-            _ => Object::Error(format!(
+            _ => Object::error(format!(
                 "Did not find value {} for {}",
                 collection.kind(),
                 index.kind()
@@ -268,13 +552,37 @@ impl Eval {
         }
     }
 
+    fn checked_index(len: usize, i: i64) -> Result<usize, Object> {
+        if i < 0 {
+            Err(Object::error("Negative indexing isn't valid"))
+        } else if i >= len as i64 {
+            Err(Object::error(format!(
+                "Index {} out of bounds for an array of length {}",
+                i, len
+            )))
+        } else {
+            Ok(i as usize)
+        }
+    }
+
+    fn check_hashable(key: &Object) -> Option<Object> {
+        if matches!(
+            key,
+            Object::Integer(_) | Object::Char(_) | Object::Str(_) | Object::Boolean(_)
+        ) {
+            None
+        } else {
+            Some(Object::error(format!("Cannot hash a {}", key.kind())))
+        }
+    }
+
     fn eval_conditional(&mut self, conditional: ConditionalOperator) -> Object {
         let condition = self.eval_expression(*conditional.cond);
         if let Object::Error(_) = condition {
             return condition;
         }
 
-        if let Object::Boolean(true) = condition {
+        if condition.truthy() {
             self.eval_block(conditional.consequence)
         } else if let Some(alternative) = conditional.alternative {
             self.eval_block(alternative)
@@ -283,16 +591,355 @@ impl Eval {
         }
     }
 
+    fn eval_while(&mut self, while_loop: WhileLoop) -> Object {
+        loop {
+            let condition = self.eval_expression(*while_loop.cond.clone());
+            if condition.errored() {
+                return condition;
+            }
+
+            if !condition.truthy() {
+                return Object::Null;
+            }
+
+            match self.eval_block(while_loop.body.clone()) {
+                Object::Break => return Object::Null,
+                Object::Continue => continue,
+                res if res.interrupts() => return res,
+                _ => (),
+            }
+        }
+    }
+
+    fn eval_for(&mut self, for_loop: ForLoop) -> Object {
+        let iterable = self.eval_expression(*for_loop.iterable);
+        if iterable.errored() {
+            return iterable;
+        }
+
+        // a lone binding over a hashmap receives the key
+        // and the element for everything else
+        let keyed = matches!(iterable, Object::HashLiteral(_));
+
+        let items: Box<dyn Iterator<Item = (Object, Object)>> = match iterable {
+            Object::Array(arr) => Box::new(
+                arr.into_iter()
+                    .enumerate()
+                    .map(|(i, elem)| (Object::Integer(i as i64), elem)),
+            ),
+            Object::Str(s) => Box::new(
+                s.chars()
+                    .enumerate()
+                    .map(|(i, ch)| (Object::Integer(i as i64), Object::Char(ch)))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            Object::HashLiteral(hmap) => {
+                let mut entries = hmap.into_iter().collect::<Vec<_>>();
+                Self::sort_entries(&mut entries);
+                Box::new(entries.into_iter())
+            }
+            Object::Range(start, end) => Box::new(
+                (start..end)
+                    .enumerate()
+                    .map(|(i, n)| (Object::Integer(i as i64), Object::Integer(n))),
+            ),
+            _ => {
+                return Object::error(format!("Cannot iterate over a {}", iterable.kind()));
+            }
+        };
+
+        for (key, elem) in items {
+            let mut env = Environment::new_enclosed_env(Rc::clone(&self.environment));
+            match for_loop.bindings.as_slice() {
+                [single] => env.upsert(single.label.clone(), if keyed { key } else { elem }),
+                [first, second] => {
+                    env.upsert(first.label.clone(), key);
+                    env.upsert(second.label.clone(), elem);
+                }
+                _ => unreachable!("for loops bind one or two variables"),
+            }
+
+            let curr_env = Rc::clone(&self.environment);
+            self.environment = Rc::new(RefCell::new(env));
+            let res = self.eval_block(for_loop.body.clone());
+            self.environment = curr_env;
+
+            match res {
+                Object::Break => break,
+                Object::Continue => continue,
+                res if res.interrupts() => return res,
+                _ => (),
+            }
+        }
+
+        Object::Null
+    }
+
+    fn eval_match(&mut self, match_expr: MatchExpression) -> Object {
+        let value = self.eval_expression(*match_expr.scrutinee);
+        if value.errored() {
+            return value;
+        }
+
+        for arm in match_expr.arms {
+            let mut bindings = Vec::new();
+            if !Self::match_pattern(&arm.pattern, &value, &mut bindings) {
+                continue;
+            }
+
+            let mut env = Environment::new_enclosed_env(Rc::clone(&self.environment));
+            for (name, val) in bindings {
+                env.upsert(name, val);
+            }
+
+            let curr_env = Rc::clone(&self.environment);
+            self.environment = Rc::new(RefCell::new(env));
+
+            let guard = match arm.guard {
+                Some(guard) => self.eval_expression(guard),
+                None => Object::Boolean(true),
+            };
+
+            let res = match guard {
+                Object::Error(_) => Some(guard),
+                _ if guard.truthy() => Some(self.eval_block(arm.body)),
+                _ => None,
+            };
+
+            self.environment = curr_env;
+
+            if let Some(res) = res {
+                return res;
+            }
+        }
+
+        Object::error(format!("No match arm for {} {}", value.kind(), value))
+    }
+
+    /// Checks the value against the pattern and
+    /// collects the names bound along the way
+    fn match_pattern(
+        pattern: &Pattern,
+        value: &Object,
+        bindings: &mut Vec<(String, Object)>,
+    ) -> bool {
+        match (pattern, value) {
+            (Pattern::Wildcard(_), _) => true,
+            (Pattern::Binding(ident), _) => {
+                bindings.push((ident.label.clone(), value.clone()));
+                true
+            }
+            (Pattern::Literal(literal), _) => {
+                Self::equals(&Self::eval_primitive(literal.val.clone()), value)
+            }
+            (Pattern::Array(pattern), Object::Array(arr)) => {
+                let (before, after) = (pattern.before.len(), pattern.after.len());
+                let fits = match pattern.rest {
+                    Some(_) => arr.len() >= before + after,
+                    None => arr.len() == before,
+                };
+                if !fits {
+                    return false;
+                }
+
+                let tail_start = arr.len() - after;
+                let matched = pattern
+                    .before
+                    .iter()
+                    .zip(&arr[..before])
+                    .chain(pattern.after.iter().zip(&arr[tail_start..]))
+                    .all(|(pattern, elem)| Self::match_pattern(pattern, elem, bindings));
+
+                if let (true, Some(Some(rest))) = (matched, &pattern.rest) {
+                    bindings.push((
+                        rest.label.clone(),
+                        Object::Array(arr[before..tail_start].to_vec()),
+                    ));
+                }
+
+                matched
+            }
+            (Pattern::HashMap(pattern), Object::HashLiteral(hmap)) => {
+                pattern.pairs.iter().all(|(key, pattern)| {
+                    match hmap.get(&Self::eval_primitive(key.val.clone())) {
+                        Some(val) => Self::match_pattern(pattern, val, bindings),
+                        None => false,
+                    }
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// The target's indexes are evaluated once, before the value,
+    /// and reused both to read the current value and to store the new one
+    fn eval_assignment(&mut self, assignment: Assignment) -> Object {
+        let (name, path) = match self.eval_target(*assignment.target) {
+            Ok(target) => target,
+            Err(err) => return err,
+        };
+
+        let compound = match assignment.operator.kind.compound_operator() {
+            Some(kind) => {
+                let curr = self.read_target(&name, &path);
+                if curr.errored() {
+                    return curr;
+                }
+                Some((kind, curr))
+            }
+            None => None,
+        };
+
+        let mut val = self.eval_expression(*assignment.val);
+        if val.errored() {
+            return val;
+        }
+
+        if let Some((kind, curr)) = compound {
+            let operator = Token {
+                kind,
+                span: assignment.operator.span,
+            };
+            val = Self::eval_infix(&operator, curr, val);
+            if val.errored() {
+                return val;
+            }
+        }
+
+        let assigned = self.assign(&name, &path, val.clone());
+        if assigned.errored() {
+            assigned
+        } else {
+            val
+        }
+    }
+
+    /// Splits a target like `grid[i][j]` into the variable
+    /// and the values of its indexes, outermost first
+    fn eval_target(&mut self, target: Expression) -> Result<(IdentNode, Vec<Object>), Object> {
+        match target {
+            Expression::Identifier(ident) => Ok((ident, Vec::new())),
+            Expression::ArrIndex(index_access) => {
+                let (name, mut path) = self.eval_target(*index_access.arr)?;
+
+                let index = self.eval_expression(*index_access.index);
+                if index.errored() {
+                    return Err(index);
+                }
+                path.push(index);
+
+                Ok((name, path))
+            }
+            _ => unreachable!("assignment targets are validated by the parser"),
+        }
+    }
+
+    fn read_target(&self, name: &IdentNode, path: &[Object]) -> Object {
+        let mut curr = self.eval_ident(name.clone());
+        for index in path {
+            if curr.errored() {
+                break;
+            }
+            curr = Self::index_into(&curr, index, false);
+        }
+
+        curr
+    }
+
+    /// Stores the value in the target.
+    /// Collections are values so assigning to an index rebuilds the
+    /// collection and assigns that to the variable holding it
+    fn assign(&mut self, name: &IdentNode, path: &[Object], val: Object) -> Object {
+        let val = if path.is_empty() {
+            val
+        } else {
+            let collection = self.eval_ident(name.clone());
+            if collection.errored() {
+                return collection;
+            }
+
+            match Self::store(collection, path, val) {
+                Ok(updated) => updated,
+                Err(err) => return err,
+            }
+        };
+
+        if self.environment.borrow_mut().update(&name.label, val) {
+            Object::Null
+        } else {
+            Object::Error(Box::new(
+                RuntimeError::from(format!(
+                    "Cannot assign to {} before it is declared",
+                    name.label
+                ))
+                .with_code("E0102")
+                .with_ident(&name.label)
+                .with_similar(self.similar_names(&name.label)),
+            ))
+        }
+    }
+
+    /// Rebuilds the collection with the value stored at the end of the path
+    fn store(collection: Object, path: &[Object], val: Object) -> Result<Object, Object> {
+        let Some((index, rest)) = path.split_first() else {
+            return Ok(val);
+        };
+
+        match (collection, index) {
+            (Object::Array(mut a), Object::Integer(i)) => {
+                let i = Self::checked_index(a.len(), *i)?;
+                let inner = std::mem::replace(&mut a[i], Object::Null);
+                a[i] = Self::store(inner, rest, val)?;
+                Ok(Object::Array(a))
+            }
+            (Object::HashLiteral(mut h), key) => {
+                if let Some(err) = Self::check_hashable(key) {
+                    return Err(err);
+                }
+                let inner = h.remove(key).unwrap_or(Object::Null);
+                h.insert(key.clone(), Self::store(inner, rest, val)?);
+                Ok(Object::HashLiteral(h))
+            }
+            (collection, index) => Err(Object::error(format!(
+                "Cannot assign to a {} index of {}",
+                index.kind(),
+                collection.kind()
+            ))),
+        }
+    }
+
     fn eval_ident(&self, ident: IdentNode) -> Object {
-        match self.environment.borrow_mut().get(&ident.label) {
+        let found = self.environment.borrow().get(&ident.label);
+
+        match found {
             Some(id) => id,
             None => match BuiltinFunction::try_builtin(&ident.label) {
                 Some(blt) => blt,
-                None => Object::Error("Identifier not found".into()),
+                None => Object::Error(Box::new(
+                    RuntimeError::from(format!("Identifier not found: {}", ident.label))
+                        .with_code("E0101")
+                        .with_ident(&ident.label)
+                        .with_similar(self.similar_names(&ident.label)),
+                )),
             },
         }
     }
 
+    /// Visible bindings and builtins spelled like the name
+    fn similar_names(&self, name: &str) -> Vec<String> {
+        let names = self.environment.borrow().names();
+        let candidates = names
+            .iter()
+            .map(String::as_str)
+            .chain(BuiltinFunction::NAMES.iter().copied());
+
+        closest(name, candidates)
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
     fn eval_expressions(&mut self, expressions: Vec<Expression>) -> Vec<Object> {
         let mut res = Vec::new();
 
@@ -314,6 +961,7 @@ impl Eval {
             PrimitiveNode::CharLiteral(c) => Object::Char(c),
             PrimitiveNode::StringLiteral(s) => Object::Str(s),
             PrimitiveNode::BooleanLiteral(b) => Object::Boolean(b),
+            PrimitiveNode::NullLiteral => Object::Null,
         }
     }
 
@@ -321,31 +969,50 @@ impl Eval {
         match operator.kind {
             TokenKind::Bang => Self::eval_bang_expr(right),
             TokenKind::Minus => Self::eval_minus_expr(right),
-            _ => Object::Error(format!("Invalid operator: {}", operator)),
+            TokenKind::BitNot => Self::eval_bit_not_expr(right),
+            _ => Object::error(format!("Invalid operator: {}", operator.kind)),
         }
     }
 
     pub fn eval_bang_expr(right: &Object) -> Object {
-        let truth_value: bool = if let Object::Boolean(b) = right {
-            *b
-        } else {
-            !(matches!(right, Object::Null))
-        };
-
-        Object::Boolean(!truth_value)
+        Object::Boolean(!right.truthy())
     }
 
     pub fn eval_minus_expr(right: &Object) -> Object {
         match right {
-            Object::Integer(i) => Object::Integer(-i),
+            Object::Integer(i) => match i.checked_neg() {
+                Some(neg) => Object::Integer(neg),
+                None => Self::overflow(format!("-({})", i)),
+            },
             Object::Float(f) => Object::Float(-f),
-            _ => Object::Error("Invalid datatype".into()),
+            _ => Object::error("Invalid datatype"),
+        }
+    }
+
+    pub fn eval_bit_not_expr(right: &Object) -> Object {
+        match right {
+            Object::Integer(i) => Object::Integer(!i),
+            _ => Object::error("Invalid datatype"),
         }
     }
 
     pub fn eval_infix(operator: &Token, left: Object, right: Object) -> Object {
+        match operator.kind {
+            TokenKind::And => return Object::Boolean(left.truthy() && right.truthy()),
+            TokenKind::Or => return Object::Boolean(left.truthy() || right.truthy()),
+            TokenKind::NullCoalesce => {
+                return match left {
+                    Object::Null => right,
+                    _ => left,
+                }
+            }
+            TokenKind::Equal => return Object::Boolean(Self::equals(&left, &right)),
+            TokenKind::NotEqual => return Object::Boolean(!Self::equals(&left, &right)),
+            _ => (),
+        }
+
         // if left.kind() != right.kind() {
-        //     return Object::Error(format!(
+        //     return Object::error(format!(
         //         "{} and {} datatypes do not match",
         //         left.kind(),
         //         right.kind()
@@ -357,37 +1024,201 @@ impl Eval {
                 Self::eval_integer_infix_expr(operator, x, y)
             }
             (Object::Float(x), Object::Float(y)) => Self::eval_float_infix_expr(operator, x, y),
+            // mixed arithmetic and comparisons promote the integer to a float
+            (Object::Integer(x), Object::Float(y)) => {
+                Self::eval_float_infix_expr(operator, x as f64, y)
+            }
+            (Object::Float(x), Object::Integer(y)) => {
+                Self::eval_float_infix_expr(operator, x, y as f64)
+            }
+            (left, right)
+                if matches!(
+                    operator.kind,
+                    TokenKind::LessThan
+                        | TokenKind::GreaterThan
+                        | TokenKind::LessThanEqual
+                        | TokenKind::GreaterThanEqual
+                ) =>
+            {
+                Self::eval_ordering(operator, &left, &right)
+            }
             (Object::Boolean(x), Object::Boolean(y)) => Self::eval_bool_infix_expr(operator, x, y),
             (Object::Str(st), Object::Str(sr)) => Self::eval_str_infix_expr(operator, st, sr),
             (Object::Str(st), Object::Integer(i)) => {
                 Self::eval_str_infix_expr(operator, st, i.to_string())
             }
-            _ => Object::Error(format!(
+            _ => Object::error(format!(
                 "Cannot perform {} operation on this datatype",
-                operator
+                operator.kind
             )),
         }
     }
 
+    /// Values are equal when they have the same structure.
+    /// Integers and floats are compared by value,
+    /// any other values of different kinds are unequal
+    pub fn equals(left: &Object, right: &Object) -> bool {
+        match (left, right) {
+            (Object::Integer(x), Object::Float(y)) | (Object::Float(y), Object::Integer(x)) => {
+                *x as f64 == *y
+            }
+            (Object::Array(x), Object::Array(y)) => {
+                x.len() == y.len() && x.iter().zip(y).all(|(x, y)| Self::equals(x, y))
+            }
+            (Object::HashLiteral(x), Object::HashLiteral(y)) => {
+                x.len() == y.len()
+                    && x.iter()
+                        .all(|(key, x)| y.get(key).is_some_and(|y| Self::equals(x, y)))
+            }
+            _ => left == right,
+        }
+    }
+
+    /// Orders the entries of a hashmap by key, so that iterating over it
+    /// doesn't change from one run to the next. Keys of different kinds
+    /// are grouped by kind and those which can't be compared, like
+    /// booleans, fall back to how they're displayed
+    fn sort_entries(entries: &mut [(Object, Object)]) {
+        entries.sort_by(|(x, _), (y, _)| {
+            x.kind().cmp(y.kind()).then_with(|| {
+                Self::compare(x, y).unwrap_or_else(|| x.to_string().cmp(&y.to_string()))
+            })
+        });
+    }
+
+    /// Numbers are ordered by value, strings and characters by their
+    /// code points and arrays element by element, the shorter one first
+    /// when one is a prefix of the other
+    pub fn compare(left: &Object, right: &Object) -> Option<Ordering> {
+        match (left, right) {
+            (Object::Integer(x), Object::Integer(y)) => Some(x.cmp(y)),
+            (Object::Integer(x), Object::Float(y)) => (*x as f64).partial_cmp(y),
+            (Object::Float(x), Object::Integer(y)) => x.partial_cmp(&(*y as f64)),
+            (Object::Float(x), Object::Float(y)) => x.partial_cmp(y),
+            (Object::Str(x), Object::Str(y)) => Some(x.cmp(y)),
+            (Object::Char(x), Object::Char(y)) => Some(x.cmp(y)),
+            (Object::Array(x), Object::Array(y)) => {
+                for (x, y) in x.iter().zip(y) {
+                    match Self::compare(x, y)? {
+                        Ordering::Equal => continue,
+                        ordering => return Some(ordering),
+                    }
+                }
+
+                Some(x.len().cmp(&y.len()))
+            }
+            _ => None,
+        }
+    }
+
+    fn eval_ordering(operator: &Token, left: &Object, right: &Object) -> Object {
+        let Some(ordering) = Self::compare(left, right) else {
+            return Object::error(format!(
+                "Cannot compare {} with {}",
+                left.kind(),
+                right.kind()
+            ));
+        };
+
+        let res = match operator.kind {
+            TokenKind::LessThan => ordering.is_lt(),
+            TokenKind::GreaterThan => ordering.is_gt(),
+            TokenKind::LessThanEqual => ordering.is_le(),
+            TokenKind::GreaterThanEqual => ordering.is_ge(),
+            _ => unreachable!("{} isn't a comparison", operator),
+        };
+
+        Object::Boolean(res)
+    }
+
+    /// Integer arithmetic is checked. Dividing by zero and results that
+    /// don't fit in 64 bits are runtime errors, nothing wraps around or
+    /// turns into a float. Floats follow IEEE 754 so `1.0 / 0.0` is infinite.
+    /// `~/` rounds the quotient towards negative infinity, `/` towards zero
     pub fn eval_integer_infix_expr(operator: &Token, x: i64, y: i64) -> Object {
-        match operator.kind {
-            TokenKind::Plus => Object::Integer(x + y),
-            TokenKind::Minus => Object::Integer(x - y),
-            TokenKind::ForwardSlash => Object::Integer(x / y),
-            TokenKind::Modulo => Object::Integer(x % y),
-            TokenKind::Asterisk => Object::Integer(x * y),
-            TokenKind::Equal => Object::Boolean(x == y),
-            TokenKind::NotEqual => Object::Boolean(x != y),
-            TokenKind::LessThan => Object::Boolean(x < y),
-            TokenKind::GreaterThan => Object::Boolean(x > y),
-            TokenKind::LessThanEqual => Object::Boolean(x <= y),
-            TokenKind::GreaterThanEqual => Object::Boolean(x >= y),
+        let result = match operator.kind {
+            TokenKind::Plus => x.checked_add(y),
+            TokenKind::Minus => x.checked_sub(y),
+            TokenKind::Asterisk => x.checked_mul(y),
+            TokenKind::Power if y < 0 => {
+                return Object::error(format!(
+                    "Negative exponent: {} ** {} isn't an integer, use a float base instead",
+                    x, y
+                ))
+            }
+            TokenKind::Power => match u32::try_from(y) {
+                Ok(exp) => x.checked_pow(exp),
+                // only 0, 1 and -1 stay in range for exponents this large
+                Err(_) if (-1..=1).contains(&x) => Some(x.pow(2 - (y % 2) as u32)),
+                Err(_) => None,
+            },
+            TokenKind::ForwardSlash | TokenKind::Modulo | TokenKind::IntDivide if y == 0 => {
+                return Object::Error(Box::new(
+                    RuntimeError::from(format!(
+                        "Division by zero: {} {} 0",
+                        x,
+                        operator.kind.text().unwrap_or_default()
+                    ))
+                    .with_code("E0105"),
+                ))
+            }
+            TokenKind::ForwardSlash => x.checked_div(y),
+            TokenKind::IntDivide => x.checked_div(y).map(|q| {
+                if x % y != 0 && (x < 0) != (y < 0) {
+                    q - 1
+                } else {
+                    q
+                }
+            }),
+            // the remainder always fits, even when the quotient doesn't
+            TokenKind::Modulo => Some(x.wrapping_rem(y)),
+            TokenKind::BitAnd => Some(x & y),
+            TokenKind::BitOr => Some(x | y),
+            TokenKind::BitXor => Some(x ^ y),
+            TokenKind::ShiftLeft | TokenKind::ShiftRight if !(0..64).contains(&y) => {
+                return Object::error(format!(
+                    "Shift out of range: {} {} {} must shift by 0 to 63 bits",
+                    x,
+                    operator.kind.text().unwrap_or_default(),
+                    y
+                ))
+            }
+            // shifting bits out of a 64 bit integer is an overflow
+            TokenKind::ShiftLeft => Some(x << y).filter(|shifted| shifted >> y == x),
+            TokenKind::ShiftRight => Some(x >> y),
+            TokenKind::Equal => return Object::Boolean(x == y),
+            TokenKind::NotEqual => return Object::Boolean(x != y),
+            TokenKind::LessThan => return Object::Boolean(x < y),
+            TokenKind::GreaterThan => return Object::Boolean(x > y),
+            TokenKind::LessThanEqual => return Object::Boolean(x <= y),
+            TokenKind::GreaterThanEqual => return Object::Boolean(x >= y),
+            TokenKind::DotDot => return Object::Range(x, y),
             _ => {
                 unreachable!("{}", format!("No infix for {}", operator))
             }
+        };
+
+        match result {
+            Some(i) => Object::Integer(i),
+            None => Self::overflow(format!(
+                "{} {} {}",
+                x,
+                operator.kind.text().unwrap_or_default(),
+                y
+            )),
         }
     }
 
+    fn overflow(expr: String) -> Object {
+        Object::Error(Box::new(
+            RuntimeError::from(format!(
+                "Integer overflow: {} doesn't fit in a 64 bit integer",
+                expr
+            ))
+            .with_code("E0104"),
+        ))
+    }
+
     pub fn eval_float_infix_expr(operator: &Token, x: f64, y: f64) -> Object {
         match operator.kind {
             TokenKind::Plus => Object::Float(x + y),
@@ -395,16 +1226,18 @@ impl Eval {
             TokenKind::ForwardSlash => Object::Float(x / y),
             TokenKind::Modulo => Object::Float(x % y),
             TokenKind::Asterisk => Object::Float(x * y),
+            TokenKind::Power => Object::Float(x.powf(y)),
+            TokenKind::IntDivide => Object::Float((x / y).floor()),
             TokenKind::Equal => Object::Boolean(x == y),
             TokenKind::NotEqual => Object::Boolean(x != y),
             TokenKind::LessThan => Object::Boolean(x < y),
             TokenKind::GreaterThan => Object::Boolean(x > y),
             TokenKind::LessThanEqual => Object::Boolean(x <= y),
             TokenKind::GreaterThanEqual => Object::Boolean(x >= y),
-            _ => {
-                println!("{}", operator);
-                unreachable!("No infix for float")
-            }
+            _ => Object::error(format!(
+                "{} operation cannot be performed on floats",
+                operator
+            )),
         }
     }
 
@@ -412,9 +1245,10 @@ impl Eval {
         match operator.kind {
             TokenKind::Equal => Object::Boolean(left == right),
             TokenKind::NotEqual => Object::Boolean(left != right),
-            TokenKind::And => Object::Boolean(left && right),
-            TokenKind::Or => Object::Boolean(left || right),
-            _ => unreachable!("No infix for bool"),
+            _ => Object::error(format!(
+                "{} operation cannot be performed on booleans",
+                operator
+            )),
         }
     }
 
@@ -431,10 +1265,10 @@ impl Eval {
                             .to_string(),
                     )
                 } else {
-                    Object::Error(format!("Cannot subtract {} from {}", right, left))
+                    Object::error(format!("Cannot subtract {} from {}", right, left))
                 }
             }
-            _ => Object::Error(format!(
+            _ => Object::error(format!(
                 "{} operation cannot be performed on strings",
                 operator
             )),
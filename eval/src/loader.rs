@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use lace_lexer::Lexer;
+use lace_parser::{ast::Program, diagnostic::Emitter, Parser};
+
+use crate::object::Object;
+
+/// Environment variable holding extra directories to look for sourced files in
+pub const SEARCH_PATH_VAR: &str = "LACE_PATH";
+
+/// Finds, parses and keeps track of sourced files
+pub struct Loader {
+    /// directories searched after the one holding the current file
+    pub search_path: Vec<PathBuf>,
+    /// how errors in sourced files are reported
+    pub emitter: Emitter,
+    /// files being evaluated, the innermost one last
+    active: Vec<PathBuf>,
+    /// programs which have already been parsed
+    programs: HashMap<PathBuf, Program>,
+    /// modules which have already been imported
+    modules: HashMap<PathBuf, Object>,
+}
+
+impl Default for Loader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Loader {
+    pub fn new() -> Self {
+        let search_path = match env::var_os(SEARCH_PATH_VAR) {
+            Some(paths) => env::split_paths(&paths).collect(),
+            None => Vec::new(),
+        };
+
+        Self {
+            search_path,
+            emitter: Emitter::default(),
+            active: Vec::new(),
+            programs: HashMap::new(),
+            modules: HashMap::new(),
+        }
+    }
+
+    /// Resolves the path and parses the file unless it was parsed before.
+    /// Sourcing a file that is still being evaluated is a cycle
+    pub fn load(&mut self, path: &Path) -> Result<(PathBuf, Program), Object> {
+        let file = self.resolve(path)?;
+
+        if let Some(pos) = self.active.iter().position(|active| *active == file) {
+            let cycle = self.active[pos..]
+                .iter()
+                .chain([&file])
+                .map(|file| file.display().to_string())
+                .collect::<Vec<String>>();
+
+            return Err(Object::error(format!(
+                "Import cycle detected: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        if let Some(program) = self.programs.get(&file) {
+            return Ok((file, program.clone()));
+        }
+
+        let code = fs::read_to_string(&file)
+            .map_err(|err| Object::error(format!("Failed to read {}: {}", file.display(), err)))?;
+
+        let lexer = Lexer::new(code);
+        let mut parser = Parser::for_file(lexer, file.clone());
+        let program = parser.parse_program();
+
+        if !parser.errors.is_empty() {
+            // nothing sensible can be done when standard error is gone
+            let _ = parser.emit_errors(&mut io::stderr(), &self.emitter);
+            return Err(Object::error(format!(
+                "Couldn't source {} because it has errors",
+                file.display()
+            )));
+        }
+
+        self.programs.insert(file.clone(), program.clone());
+
+        Ok((file, program))
+    }
+
+    pub fn cached_module(&self, path: &Path) -> Option<Object> {
+        let file = self.resolve(path).ok()?;
+        self.modules.get(&file).cloned()
+    }
+
+    pub fn cache_module(&mut self, file: PathBuf, module: Object) {
+        self.modules.insert(file, module);
+    }
+
+    /// Marks the file as being evaluated
+    pub fn enter(&mut self, file: PathBuf) {
+        self.active.push(file);
+    }
+
+    pub fn exit(&mut self) {
+        self.active.pop();
+    }
+
+    /// The innermost file being evaluated
+    pub fn current(&self) -> Option<&PathBuf> {
+        self.active.last()
+    }
+
+    /// Looks for the file next to the one currently being evaluated
+    /// (or the working directory when there is none) and then in the search path
+    fn resolve(&self, path: &Path) -> Result<PathBuf, Object> {
+        let mut path = path.to_path_buf();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("lace") {
+            path.as_mut_os_string().push(".lace");
+        }
+
+        let base = match self.active.last().and_then(|file| file.parent()) {
+            Some(dir) => dir.to_path_buf(),
+            None => env::current_dir().unwrap_or_default(),
+        };
+
+        let candidates = if path.is_absolute() {
+            vec![path.clone()]
+        } else {
+            [base]
+                .iter()
+                .chain(&self.search_path)
+                .map(|dir| dir.join(&path))
+                .collect()
+        };
+
+        candidates
+            .iter()
+            .find(|candidate| candidate.is_file())
+            .and_then(|found| found.canonicalize().ok())
+            .ok_or_else(|| {
+                let searched = candidates
+                    .iter()
+                    .map(|candidate| candidate.display().to_string())
+                    .collect::<Vec<String>>();
+
+                Object::error(format!(
+                    "Couldn't find {}. Looked in {}",
+                    path.display(),
+                    searched.join(", ")
+                ))
+            })
+    }
+}
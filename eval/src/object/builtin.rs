@@ -2,25 +2,60 @@ use std::fmt::Display;
 
 use crate::{lace_lib, Object};
 
-#[derive(PartialEq, Clone)]
-pub enum BuiltinFunction {
-    Kind,
-    Write,
-    Read,
-    Len,
-    First,
-    Last,
-    Split,
-    Chars,
-    Append,
-    StripStart,
-    StripEnd,
+/// Declares the builtins from a single table so that their
+/// names, lookup and display can't drift apart
+macro_rules! builtins {
+    ($($variant:ident => $name:literal),+ $(,)?) => {
+        #[derive(PartialEq, Clone)]
+        pub enum BuiltinFunction {
+            $($variant),+
+        }
+
+        impl BuiltinFunction {
+            pub const NAMES: &'static [&'static str] = &[$($name),+];
+
+            /// Name the builtin is called by
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(BuiltinFunction::$variant => $name),+
+                }
+            }
+
+            fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(BuiltinFunction::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+builtins! {
+    Kind => "kind",
+    Write => "write",
+    Read => "read",
+    Len => "len",
+    First => "first",
+    Last => "last",
+    Split => "split",
+    Chars => "chars",
+    Append => "append",
+    StripStart => "strip_start",
+    StripEnd => "strip_end",
+    Keys => "keys",
+    Values => "values",
+    Int => "int",
+    Float => "float",
+    Round => "round",
+    Floor => "floor",
+    Ceil => "ceil",
 }
 
 macro_rules! check_n_args {
     ($expected:expr,$received:expr) => {
         if $expected != $received {
-            return Object::Error(format!(
+            return Object::error(format!(
                 "Incorrect number of arguments provided. Expected {}, Received {}.",
                 $expected, $received
             ));
@@ -30,22 +65,7 @@ macro_rules! check_n_args {
 
 impl Display for BuiltinFunction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut disp = String::from("Builtin ");
-        let func = match self {
-            BuiltinFunction::Kind => "kind",
-            BuiltinFunction::Write => "write",
-            BuiltinFunction::Read => "read",
-            BuiltinFunction::Len => "len",
-            BuiltinFunction::First => "first",
-            BuiltinFunction::Last => "last",
-            BuiltinFunction::Split => "split",
-            BuiltinFunction::Chars => "chars",
-            BuiltinFunction::Append => "append",
-            BuiltinFunction::StripStart => "strip_start",
-            BuiltinFunction::StripEnd => "strip_end",
-        };
-        disp.push_str(func);
-        write!(f, "{}", disp)
+        write!(f, "Builtin {}", self.name())
     }
 }
 
@@ -67,6 +87,13 @@ impl BuiltinFunction {
                 lace_lib::std::strip_start(args[0].clone(), args[1].clone())
             }
             BuiltinFunction::StripEnd => lace_lib::std::strip_end(args[0].clone(), args[1].clone()),
+            BuiltinFunction::Keys => lace_lib::hashmap::keys(args[0].clone()),
+            BuiltinFunction::Values => lace_lib::hashmap::values(args[0].clone()),
+            BuiltinFunction::Int => lace_lib::num::int(args[0].clone()),
+            BuiltinFunction::Float => lace_lib::num::float(args[0].clone()),
+            BuiltinFunction::Round => lace_lib::num::round(args[0].clone()),
+            BuiltinFunction::Floor => lace_lib::num::floor(args[0].clone()),
+            BuiltinFunction::Ceil => lace_lib::num::ceil(args[0].clone()),
         }
     }
 
@@ -78,7 +105,14 @@ impl BuiltinFunction {
             | BuiltinFunction::Len
             | BuiltinFunction::First
             | BuiltinFunction::Last
-            | BuiltinFunction::Chars => 1,
+            | BuiltinFunction::Chars
+            | BuiltinFunction::Keys
+            | BuiltinFunction::Values
+            | BuiltinFunction::Int
+            | BuiltinFunction::Float
+            | BuiltinFunction::Round
+            | BuiltinFunction::Floor
+            | BuiltinFunction::Ceil => 1,
             BuiltinFunction::Split
             | BuiltinFunction::Append
             | BuiltinFunction::StripStart
@@ -87,23 +121,6 @@ impl BuiltinFunction {
     }
 
     pub fn try_builtin(name: &str) -> Option<Object> {
-        let func = match name {
-            "kind" => BuiltinFunction::Kind,
-            "write" => BuiltinFunction::Write,
-            "read" => BuiltinFunction::Read,
-            "len" => BuiltinFunction::Len,
-            "first" => BuiltinFunction::First,
-            "last" => BuiltinFunction::Last,
-            "chars" => BuiltinFunction::Chars,
-            "split" => BuiltinFunction::Split,
-            "append" => BuiltinFunction::Append,
-            "strip_start" => BuiltinFunction::StripStart,
-            "strip_end" => BuiltinFunction::StripEnd,
-            _ => {
-                return None;
-            }
-        };
-
-        Some(Object::Builtin(func))
+        Self::from_name(name).map(Object::Builtin)
     }
 }
@@ -0,0 +1,141 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use lace_lexer::{
+    token::span::{dummy_span, Span},
+    Lexer,
+};
+use lace_parser::diagnostic::{Diagnostic, Emitter, Label};
+
+/// A function call which was being evaluated when the error occured
+#[derive(PartialEq, Debug, Clone)]
+pub struct Frame {
+    pub name: String,
+    /// where the function was called from
+    pub span: Option<Span>,
+    pub file: Option<PathBuf>,
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "in {}", self.name)?;
+
+        if let Some(span) = &self.span {
+            write!(f, ", called at {}:{}", span.start_line, span.start_col + 1)?;
+        }
+
+        match &self.file {
+            Some(file) => write!(f, " in {}", file.display()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct RuntimeError {
+    pub code: &'static str,
+    pub msg: String,
+    /// the innermost expression which failed
+    pub span: Option<Span>,
+    pub file: Option<PathBuf>,
+    /// the identifier which caused the error, if any
+    pub ident: Option<String>,
+    /// names in scope spelled like the identifier, best match first
+    pub similar: Vec<String>,
+    /// function calls leading up to the error, the innermost one first
+    pub trace: Vec<Frame>,
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl From<String> for RuntimeError {
+    fn from(msg: String) -> Self {
+        Self {
+            code: "E0100",
+            msg,
+            span: None,
+            file: None,
+            ident: None,
+            similar: Vec::new(),
+            trace: Vec::new(),
+        }
+    }
+}
+
+impl From<&str> for RuntimeError {
+    fn from(msg: &str) -> Self {
+        Self::from(msg.to_string())
+    }
+}
+
+impl RuntimeError {
+    pub fn with_ident(mut self, ident: &str) -> Self {
+        self.ident = Some(ident.into());
+        self
+    }
+
+    pub fn with_similar(mut self, similar: Vec<String>) -> Self {
+        self.similar = similar;
+        self
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Calls made from the same file point at their call site,
+    /// the others are only described
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::error(self.code, self.msg.clone());
+
+        if let Some(span) = &self.span {
+            diagnostic = diagnostic.with_label(Label::primary(span.clone(), ""));
+        }
+
+        let span = self.span.clone().unwrap_or_else(dummy_span);
+        for name in &self.similar {
+            diagnostic =
+                diagnostic.with_suggestion(format!("did you mean `{}`?", name), span.clone(), name);
+        }
+
+        self.trace
+            .iter()
+            .fold(diagnostic, |diagnostic, frame| match &frame.span {
+                Some(span) if frame.file == self.file => diagnostic.with_label(Label::secondary(
+                    span.clone(),
+                    format!("{} was called here", frame.name),
+                )),
+                _ => diagnostic.with_note(frame.to_string()),
+            })
+    }
+
+    /// Prints the error the same way the parser prints its errors.
+    /// The source is only used when the error didn't occur in a file
+    pub fn log(&self, source: &str) {
+        // nothing sensible can be done when standard output is gone
+        let _ = self.emit(&mut io::stdout(), source, &Emitter::default());
+    }
+
+    pub fn emit(&self, out: &mut impl Write, source: &str, emitter: &Emitter) -> io::Result<()> {
+        let code = match &self.file {
+            Some(file) => fs::read_to_string(file).unwrap_or_else(|_| source.into()),
+            None => source.into(),
+        };
+
+        emitter.emit(
+            out,
+            &self.diagnostic(),
+            &Lexer::new(code),
+            self.file.as_deref(),
+        )
+    }
+}
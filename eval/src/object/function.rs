@@ -1,14 +1,26 @@
-use std::{cell::RefCell, fmt::Display, rc::Rc};
+use std::{cell::RefCell, fmt::Display, path::PathBuf, rc::Rc};
 
 use lace_parser::ast::{nodes::IdentNode, statement::BlockStatement};
 
 use crate::environment::Environment;
 
-#[derive(PartialEq, Clone)]
+#[derive(Clone)]
 pub struct Function {
+    pub name: Option<String>,
     pub params: Vec<IdentNode>,
-    pub body: BlockStatement,
+    /// shared so that copying a function around doesn't copy its body
+    pub body: Rc<BlockStatement>,
     pub environment: Rc<RefCell<Environment>>,
+    /// file the function was defined in, if any
+    pub file: Option<PathBuf>,
+}
+
+/// Functions are equal only when they are the same closure. Comparing their
+/// environments would never end, since a named function is stored in its own
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.body, &other.body) && Rc::ptr_eq(&self.environment, &other.environment)
+    }
 }
 
 impl Display for Function {
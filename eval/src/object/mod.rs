@@ -1,9 +1,11 @@
 pub mod builtin;
+pub mod error;
 pub mod function;
+pub mod module;
 
 use std::{collections::HashMap, fmt::Display, hash::Hash};
 
-use self::{builtin::BuiltinFunction, function::Function};
+use self::{builtin::BuiltinFunction, error::RuntimeError, function::Function, module::Module};
 
 #[derive(PartialEq, Clone)]
 pub enum Object {
@@ -14,11 +16,16 @@ pub enum Object {
     Boolean(bool),
     Function(Function),
     Builtin(BuiltinFunction),
+    Module(Module),
     Array(Vec<Object>),
     HashLiteral(HashMap<Object, Object>),
+    /// Half open range of integers
+    Range(i64, i64),
     Return(Box<Object>),
+    Break,
+    Continue,
     Null,
-    Error(String),
+    Error(Box<RuntimeError>),
 }
 
 impl Display for Object {
@@ -31,6 +38,7 @@ impl Display for Object {
             Object::Boolean(b) => write!(f, "{}", b),
             Object::Function(func) => write!(f, "{}", func),
             Object::Builtin(bfunc) => write!(f, "{}", bfunc),
+            Object::Module(module) => write!(f, "{}", module),
             Object::Array(arr) => {
                 let elements = arr.iter().map(ToString::to_string).collect::<Vec<String>>();
                 write!(f, "[{}]", elements.join(", "))
@@ -42,7 +50,10 @@ impl Display for Object {
                     .collect::<Vec<String>>();
                 write!(f, "{{ {} }}", pairs.join(", "))
             }
+            Object::Range(start, end) => write!(f, "{}..{}", start, end),
             Object::Return(obj) => write!(f, "{}", obj),
+            Object::Break => write!(f, "break"),
+            Object::Continue => write!(f, "continue"),
             Object::Null => write!(f, "NULL"),
             Object::Error(err) => write!(f, "Err => {}", err),
         }
@@ -59,17 +70,43 @@ impl Object {
             Object::Boolean(_) => "Boolean",
             Object::Function(_) => "Function",
             Object::Builtin(_) => "Builtin Function",
+            Object::Module(_) => "Module",
             Object::Array(_) => "Array",
             Object::HashLiteral(_) => "HashMap",
+            Object::Range(_, _) => "Range",
             Object::Return(_) => "RETURN",
+            Object::Break => "BREAK",
+            Object::Continue => "CONTINUE",
             Object::Null => "NULL",
             Object::Error(_) => "ERROR",
         }
     }
 
+    pub fn error(msg: impl Into<String>) -> Self {
+        Object::Error(Box::new(RuntimeError::from(msg.into())))
+    }
+
+    /// Only false and null are falsy
+    pub fn truthy(&self) -> bool {
+        match self {
+            Object::Boolean(b) => *b,
+            Object::Null => false,
+            _ => true,
+        }
+    }
+
     pub fn errored(&self) -> bool {
         matches!(self, Object::Error(_))
     }
+
+    /// Whether this object should stop the evaluation of a block
+    /// and be passed on to whatever encloses it
+    pub fn interrupts(&self) -> bool {
+        matches!(
+            self,
+            Object::Error(_) | Object::Return(_) | Object::Break | Object::Continue
+        )
+    }
 }
 
 impl Eq for Object {}
@@ -0,0 +1,19 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::object::Object;
+
+/// Bindings exported by an imported file
+#[derive(PartialEq, Clone)]
+pub struct Module {
+    pub name: String,
+    pub members: HashMap<String, Object>,
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut members = self.members.keys().cloned().collect::<Vec<String>>();
+        members.sort();
+
+        write!(f, "Module {} {{ {} }}", self.name, members.join(", "))
+    }
+}
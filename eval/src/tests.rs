@@ -0,0 +1,866 @@
+use std::{env, fs, path::PathBuf};
+
+use lace_lexer::Lexer;
+use lace_parser::Parser;
+
+use crate::{
+    object::{builtin::BuiltinFunction, Object},
+    Eval,
+};
+
+fn eval_input(input: &str) -> Object {
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    assert_eq!(parser.errors.len(), 0);
+
+    Eval::new().eval(program)
+}
+
+fn validate_eval(input: &str, expected: Object) {
+    let received = eval_input(input);
+    println!("expected: {}, received: {}", expected, received);
+    assert!(received == expected);
+}
+
+fn validate_eval_err(input: &str) {
+    let received = eval_input(input);
+    println!("received: {}", received);
+    assert!(received.errored());
+}
+
+#[test]
+fn will_you_loop() {
+    let input = r#"
+        let i = 0;
+        let sum = 0;
+        while (i < 10) {
+            let i = i + 1;
+            if (i == 3) {
+                continue;
+            }
+            if (i == 8) {
+                break;
+            }
+            let sum = sum + i;
+        }
+        sum
+    "#;
+
+    validate_eval(input, Object::Integer(1 + 2 + 4 + 5 + 6 + 7));
+}
+
+#[test]
+fn will_you_return_from_a_loop() {
+    let input = r#"
+        let find = fn(arr, needle) {
+            let i = 0;
+            while (len(arr) > i) {
+                if (arr[i] == needle) {
+                    return i;
+                }
+                let i = i + 1;
+            }
+            return -1;
+        };
+        find([4, 8, 15, 16], 15) + find([1], 2)
+    "#;
+
+    validate_eval(input, Object::Integer(1));
+}
+
+#[test]
+fn detect_stray_break() {
+    validate_eval_err("break;");
+    validate_eval_err("let f = fn() { continue; }; f();");
+}
+
+#[test]
+fn will_you_iterate() {
+    let input = r#"
+        let find = fn(haystack, needle) {
+            for (key, x in haystack) {
+                if (x == needle) {
+                    return key;
+                }
+            }
+            return -1;
+        };
+        let first_kind = fn(iterable) {
+            for (x in iterable) {
+                return kind(x);
+            }
+        };
+        [
+            find([4, 8, 15], 15),
+            find({ "a": 1, "b": 2 }, 2),
+            find(0..10, 20),
+            first_kind("lace"),
+            first_kind({ 1: "one" })
+        ]
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(vec![
+            Object::Integer(2),
+            Object::Str("b".into()),
+            Object::Integer(-1),
+            Object::Str("Character".into()),
+            Object::Str("Integer".into()),
+        ]),
+    );
+}
+
+#[test]
+fn will_you_iterate_a_hashmap_in_order() {
+    let input = r#"
+        let keys = [];
+        for (k, v in { "b": 1, 10: 0, "a": 1, 2: 0, 'c': 0, true: 0, false: 1 }) {
+            keys = append(keys, k);
+        }
+        keys
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(vec![
+            Object::Boolean(false),
+            Object::Boolean(true),
+            Object::Char('c'),
+            Object::Integer(2),
+            Object::Integer(10),
+            Object::Str("a".into()),
+            Object::Str("b".into()),
+        ]),
+    );
+}
+
+#[test]
+fn will_you_control_a_for_loop() {
+    let input = r#"
+        let skip = fn() {
+            for (x in 0..10) {
+                if (x < 3) {
+                    continue;
+                }
+                return x;
+            }
+        };
+        let stop = fn() {
+            for (x in 0..10) {
+                break;
+                return x;
+            }
+        };
+        [skip(), stop()]
+    "#;
+
+    validate_eval(input, Object::Array(vec![Object::Integer(3), Object::Null]));
+}
+
+#[test]
+fn will_you_scope_loop_bindings() {
+    validate_eval_err("for (x in [1, 2]) { x }; x");
+    validate_eval_err("for (x in 5) { x }");
+}
+
+#[test]
+fn will_you_reassign() {
+    let input = r#"
+        let counter = fn() {
+            let count = 0;
+            fn() {
+                count += 1;
+            }
+        };
+        let tick = counter();
+        tick();
+        tick();
+
+        let total = 0;
+        for (x in [1, 2, 3, 4]) {
+            total += x;
+        }
+        total *= 10;
+        total -= 4;
+        total /= 2;
+        total %= 7;
+
+        let a = 0;
+        let b = 0;
+        a = b = 3;
+        [tick(), total, a, b]
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(vec![
+            Object::Integer(3),
+            Object::Integer(48 % 7),
+            Object::Integer(3),
+            Object::Integer(3),
+        ]),
+    );
+}
+
+#[test]
+fn detect_undeclared_assignment() {
+    validate_eval_err("x = 5;");
+    validate_eval_err("let f = fn() { let y = 1; }; f(); y += 1;");
+}
+
+#[test]
+fn will_you_assign_to_an_index() {
+    let input = r#"
+        let grid = [[0, 0], [0, 0]];
+        grid[1][0] = 5;
+        grid[1][0] += 2;
+
+        let m = { "a": 1 };
+        m["a"] *= 10;
+        m["b"] = [1];
+        m["b"][0] = 4;
+
+        let copy = grid;
+        copy[0][0] = 9;
+
+        [grid, m["a"], m["b"], copy[0]]
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(vec![
+            Object::Array(vec![
+                Object::Array(vec![Object::Integer(0), Object::Integer(0)]),
+                Object::Array(vec![Object::Integer(7), Object::Integer(0)]),
+            ]),
+            Object::Integer(10),
+            Object::Array(vec![Object::Integer(4)]),
+            Object::Array(vec![Object::Integer(9), Object::Integer(0)]),
+        ]),
+    );
+}
+
+#[test]
+fn will_you_evaluate_nested_indexes_once() {
+    let input = r#"
+        let calls = 0;
+        let f = fn() { calls += 1; 0 };
+        let grid = [[0, 0]];
+        grid[f()][1] = 5;
+        let m = { "a": { "b": 1 } };
+        m[["a"][f()]]["c"] = 2;
+        [calls, grid, m["a"]["c"]]
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(vec![
+            Object::Integer(2),
+            Object::Array(vec![Object::Array(vec![
+                Object::Integer(0),
+                Object::Integer(5),
+            ])]),
+            Object::Integer(2),
+        ]),
+    );
+    validate_eval_err(r#"let m = {}; m["a"]["b"] = 1;"#);
+}
+
+#[test]
+fn will_you_evaluate_compound_targets_once() {
+    let input = r#"
+        let calls = 0;
+        let f = fn() { calls += 1; calls - 1 };
+        let a = [10, 20];
+        a[f()] += 1;
+        [calls, a]
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(vec![
+            Object::Integer(1),
+            Object::Array(vec![Object::Integer(11), Object::Integer(20)]),
+        ]),
+    );
+
+    let input = r#"
+        let order = [];
+        let i = fn() { order = append(order, "index"); 0 };
+        let v = fn() { order = append(order, "value"); 1 };
+        let a = [1];
+        a[i()] += v();
+        order
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(vec![
+            Object::Str("index".into()),
+            Object::Str("value".into()),
+        ]),
+    );
+}
+
+#[test]
+fn detect_bad_index_assignment() {
+    validate_eval_err("let a = [1]; a[1] = 2;");
+    validate_eval_err("let a = [1]; a[-1] = 2;");
+    validate_eval_err(r#"let m = {}; m[[1]] = 2;"#);
+    validate_eval_err(r#"let s = "abc"; s[0] = 'x';"#);
+}
+
+#[test]
+fn will_you_call_methods() {
+    let input = r#"
+        let words = "may the lace".split(" ");
+        let obj = {
+            "scale": 3,
+            "times": fn(x) { x * 3 }
+        };
+        [
+            words.len(),
+            words.first().chars().len(),
+            [1, 2].append(3),
+            obj.times(2) + obj.scale,
+            { "k": 1 }.keys(),
+            { "k": 1 }.values()
+        ]
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(vec![
+            Object::Integer(3),
+            Object::Integer(3),
+            Object::Array(vec![
+                Object::Integer(1),
+                Object::Integer(2),
+                Object::Integer(3),
+            ]),
+            Object::Integer(9),
+            Object::Array(vec![Object::Str("k".into())]),
+            Object::Array(vec![Object::Integer(1)]),
+        ]),
+    );
+}
+
+#[test]
+fn detect_missing_members() {
+    validate_eval_err("[1].nope()");
+    validate_eval_err(r#"{ "a": 1 }.b"#);
+    validate_eval_err("5.len()");
+}
+
+#[test]
+fn will_you_handle_null() {
+    validate_eval("null", Object::Null);
+    validate_eval("null == null", Object::Boolean(true));
+    validate_eval("null ?? 1", Object::Integer(1));
+    validate_eval("false ?? 1", Object::Boolean(false));
+    validate_eval("null ?? null ?? 3", Object::Integer(3));
+    validate_eval(
+        "let calls = 0; let f = fn() { calls += 1; 2 }; 1 ?? f(); calls",
+        Object::Integer(0),
+    );
+
+    let config = r#"let config = { "db": { "port": 5432 }, "tags": [1] };"#;
+    validate_eval(
+        &format!(r#"{config} config?["db"]?["port"]"#),
+        Object::Integer(5432),
+    );
+    validate_eval(
+        &format!(r#"{config} config?["cache"]?["port"] ?? 6379"#),
+        Object::Integer(6379),
+    );
+    validate_eval(
+        &format!("{config} config.db?.port + (config?.cache?.port ?? 1)"),
+        Object::Integer(5433),
+    );
+    validate_eval(&format!("{config} config.tags?[3]"), Object::Null);
+    validate_eval(&format!(r#"{config} config["cache"]"#), Object::Null);
+    validate_eval("let x = null; x?.len()", Object::Null);
+    validate_eval("let n = null; n?.a.b", Object::Null);
+    validate_eval("let n = null; n?.a[0]", Object::Null);
+    validate_eval("let n = null; n?[0].a.len()", Object::Null);
+    validate_eval(
+        "let calls = 0; let f = fn() { calls += 1; 0 }; let n = null; n?.a[f()]; calls",
+        Object::Integer(0),
+    );
+    validate_eval("let n = null; (n?.a ?? [1])[0]", Object::Integer(1));
+    validate_eval("match (null) { null => 1, _ => 2 }", Object::Integer(1));
+
+    validate_eval_err(&format!("{config} config?.cache.port"));
+    validate_eval_err("let x = null; x[0]");
+    validate_eval_err(r#"{ null: 1 }"#);
+}
+
+#[test]
+fn will_you_chain_conditionals() {
+    let input = r#"
+        let classify = fn(n) {
+            if (n < 0) {
+                "negative"
+            } else if (n == 0) {
+                "zero"
+            } else if (n < 10) {
+                "small"
+            } else {
+                "large"
+            }
+        };
+        [classify(-5), classify(0), classify(5), classify(50)]
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(
+            ["negative", "zero", "small", "large"]
+                .into_iter()
+                .map(|s| Object::Str(s.into()))
+                .collect(),
+        ),
+    );
+}
+
+#[test]
+fn will_you_match() {
+    let input = r#"
+        let describe = fn(x) {
+            match (x) {
+                0 => "zero",
+                -1 => "minus one",
+                "hi" => "greeting",
+                true => "yes",
+                [] => "empty",
+                [only] => "one " + only,
+                [head, ..tail] if len(tail) > 1 => {
+                    let n = len(tail);
+                    "head " + head + " and " + n + " more"
+                }
+                [first, .., last] => "from " + first + " to " + last,
+                { "name": name, "tags": [_, ..] } => "tagged " + name,
+                { "name": name } => "named " + name,
+                n if n > 100 => "big",
+                _ => "other"
+            }
+        };
+        [
+            describe(0),
+            describe(-1),
+            describe("hi"),
+            describe(true),
+            describe([]),
+            describe(["a"]),
+            describe(["a", "b", "c"]),
+            describe(["a", "z"]),
+            describe({ "name": "lace", "tags": ["x"] }),
+            describe({ "name": "lace" }),
+            describe(500),
+            describe(5)
+        ]
+    "#;
+
+    validate_eval(
+        input,
+        Object::Array(
+            [
+                "zero",
+                "minus one",
+                "greeting",
+                "yes",
+                "empty",
+                "one a",
+                "head a and 2 more",
+                "from a to z",
+                "tagged lace",
+                "named lace",
+                "big",
+                "other",
+            ]
+            .into_iter()
+            .map(|s| Object::Str(s.into()))
+            .collect(),
+        ),
+    );
+}
+
+#[test]
+fn detect_unmatched_value() {
+    validate_eval_err("match (5) { 1 => 1 }");
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("lace-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn eval_file(file: PathBuf, search_path: Vec<PathBuf>) -> Object {
+    let code = fs::read_to_string(&file).unwrap();
+    let lexer = Lexer::new(code);
+    let mut parser = Parser::for_file(lexer, file.clone());
+    let program = parser.parse_program();
+
+    assert_eq!(parser.errors.len(), 0);
+
+    let mut eval = Eval::for_file(file);
+    eval.loader.search_path = search_path;
+    eval.eval(program)
+}
+
+#[test]
+fn will_you_source_relative_to_the_file() {
+    let dir = scratch_dir("relative");
+    fs::create_dir_all(dir.join("lib")).unwrap();
+    fs::create_dir_all(dir.join("shared")).unwrap();
+    fs::write(
+        dir.join("main.lace"),
+        r#"source "lib/outer"; outer + shared"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("lib/outer.lace"),
+        r#"source "inner"; source "common.lace"; let outer = inner * 2;"#,
+    )
+    .unwrap();
+    fs::write(dir.join("lib/inner.lace"), "let inner = 20;").unwrap();
+    fs::write(dir.join("shared/common.lace"), "let shared = 2;").unwrap();
+
+    let res = eval_file(dir.join("main.lace"), vec![dir.join("shared")]);
+    println!("received: {}", res);
+    assert!(res == Object::Integer(42));
+}
+
+#[test]
+fn detect_source_cycles() {
+    let dir = scratch_dir("cycle");
+    fs::write(dir.join("a.lace"), r#"source "b";"#).unwrap();
+    fs::write(dir.join("b.lace"), r#"source "a";"#).unwrap();
+
+    let res = eval_file(dir.join("a.lace"), Vec::new());
+    println!("received: {}", res);
+    assert!(matches!(res, Object::Error(err) if err.msg.starts_with("Import cycle detected")));
+}
+
+#[test]
+fn detect_missing_source() {
+    let dir = scratch_dir("missing");
+    fs::write(dir.join("main.lace"), r#"source "nowhere"; 1"#).unwrap();
+
+    let res = eval_file(dir.join("main.lace"), Vec::new());
+    println!("received: {}", res);
+    assert!(matches!(res, Object::Error(err) if err.msg.starts_with("Couldn't find nowhere.lace")));
+}
+
+#[test]
+fn will_you_import_exports() {
+    let dir = scratch_dir("import");
+    fs::write(
+        dir.join("main.lace"),
+        r#"import "math" as m; import "math" as again; m.double(m.base) + again.base"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("math.lace"),
+        r#"let scale = 2; export let base = 10; export let double = fn(x) { x * scale };"#,
+    )
+    .unwrap();
+
+    let res = eval_file(dir.join("main.lace"), Vec::new());
+    println!("received: {}", res);
+    assert!(res == Object::Integer(30));
+}
+
+#[test]
+fn detect_private_members() {
+    let dir = scratch_dir("private");
+    fs::write(dir.join("main.lace"), r#"import "math" as m; m.scale"#).unwrap();
+    fs::write(
+        dir.join("math.lace"),
+        "let scale = 2; export let base = 10;",
+    )
+    .unwrap();
+
+    let res = eval_file(dir.join("main.lace"), Vec::new());
+    println!("received: {}", res);
+    assert!(matches!(res, Object::Error(err) if err.msg == "Module math does not export scale"));
+
+    fs::write(dir.join("main.lace"), r#"import "math" as m; scale"#).unwrap();
+    let res = eval_file(dir.join("main.lace"), Vec::new());
+    assert!(res.errored());
+}
+
+#[test]
+fn will_you_locate_runtime_errors() {
+    let input =
+        "let inner = fn helper(x) { x + missing };\nlet outer = fn() { inner(1) };\nouter();";
+
+    let err = match eval_input(input) {
+        Object::Error(err) => err,
+        received => panic!("expected an error, received: {}", received),
+    };
+
+    assert_eq!(err.msg, "Identifier not found: missing");
+    assert_eq!(err.ident, Some("missing".into()));
+
+    let span = err.span.as_ref().expect("the error should be located");
+    assert_eq!((span.start_line, span.start_col), (1, 31));
+
+    let trace = err
+        .trace
+        .iter()
+        .map(|frame| {
+            let span = frame.span.as_ref().unwrap();
+            (frame.name.as_str(), span.start_line, span.start_col)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(trace, vec![("helper", 2, 19), ("outer", 3, 0)]);
+
+    let diagnostic = err.diagnostic();
+    assert_eq!(diagnostic.code, "E0101");
+    assert_eq!(
+        diagnostic
+            .labels
+            .iter()
+            .map(|label| (label.primary, label.span.start_line))
+            .collect::<Vec<_>>(),
+        vec![(true, 1), (false, 2), (false, 3)]
+    );
+}
+
+#[test]
+fn will_you_source_computed_paths() {
+    let dir = scratch_dir("computed");
+    fs::create_dir_all(dir.join("lib")).unwrap();
+    fs::write(
+        dir.join("main.lace"),
+        r#"let name = "util"; source "lib/" + name; util"#,
+    )
+    .unwrap();
+    fs::write(dir.join("lib/util.lace"), "let util = 7;").unwrap();
+
+    let res = eval_file(dir.join("main.lace"), Vec::new());
+    println!("received: {}", res);
+    assert!(res == Object::Integer(7));
+
+    fs::write(dir.join("main.lace"), "let name = 5; source name;").unwrap();
+
+    let res = eval_file(dir.join("main.lace"), Vec::new());
+    println!("received: {}", res);
+    assert!(matches!(res, Object::Error(err)
+        if err.msg == "Expected a path to source, received Integer" && err.span.is_some()));
+}
+
+#[test]
+fn will_you_suggest_similar_names() {
+    let similar = |input: &str| match eval_input(input) {
+        Object::Error(err) => (err.ident.clone().unwrap(), err.similar.clone()),
+        received => panic!("expected an error, received: {}", received),
+    };
+
+    assert_eq!(
+        similar("let count = 1; let counter = 2; cuont + 1"),
+        ("cuont".into(), vec!["count".into()])
+    );
+    assert_eq!(similar("lne([1])"), ("lne".into(), vec!["len".into()]));
+    assert_eq!(
+        similar("let count = 1; let f = fn() { cont = 3 }; f()"),
+        ("cont".into(), vec!["count".into()])
+    );
+    assert_eq!(similar("zzzzzz"), ("zzzzzz".into(), Vec::new()));
+
+    let Object::Error(err) = eval_input("let value = 1;\nvlaue") else {
+        panic!("expected an error");
+    };
+    let suggestion = &err.diagnostic().suggestions[0];
+    assert_eq!(suggestion.message, "did you mean `value`?");
+    assert_eq!(suggestion.replacement, "value");
+    assert_eq!(
+        (suggestion.span.start_line, suggestion.span.start_col),
+        (2, 0)
+    );
+}
+
+#[test]
+fn will_you_look_up_every_builtin() {
+    for name in BuiltinFunction::NAMES {
+        match BuiltinFunction::try_builtin(name) {
+            Some(Object::Builtin(builtin)) => assert_eq!(builtin.name(), *name),
+            _ => panic!("{} isn't a builtin", name),
+        }
+    }
+}
+
+#[test]
+fn will_you_check_arithmetic() {
+    let min = "(-9223372036854775807 - 1)";
+
+    let code = |input: &str| match eval_input(input) {
+        Object::Error(err) => err.code,
+        received => panic!("expected an error, received: {}", received),
+    };
+
+    assert_eq!(code("1 / 0"), "E0105");
+    assert_eq!(code("5 % 0"), "E0105");
+    assert_eq!(code("let x = 4; x /= 0; x"), "E0105");
+    assert_eq!(code("9223372036854775807 + 1"), "E0104");
+    assert_eq!(code(&format!("{min} - 1")), "E0104");
+    assert_eq!(code("4611686018427387904 * 2"), "E0104");
+    assert_eq!(code(&format!("-{min}")), "E0104");
+    assert_eq!(code(&format!("{min} / -1")), "E0104");
+
+    validate_eval(&format!("{min} % -1"), Object::Integer(0));
+    validate_eval(&format!("{min} + 1"), Object::Integer(i64::MIN + 1));
+    validate_eval("-9223372036854775807", Object::Integer(-i64::MAX));
+    validate_eval("1.0 / 0.0", Object::Float(f64::INFINITY));
+}
+
+#[test]
+fn will_you_promote_numbers() {
+    validate_eval("1 + 2.5", Object::Float(3.5));
+    validate_eval("2.5 * 2", Object::Float(5.0));
+    validate_eval("7 / 2.0", Object::Float(3.5));
+    validate_eval("1 < 1.5", Object::Boolean(true));
+    validate_eval("2 == 2.0", Object::Boolean(true));
+    validate_eval("let x = 1; x += 0.5; x", Object::Float(1.5));
+
+    validate_eval("int(3.9)", Object::Integer(3));
+    validate_eval("int(-3.9)", Object::Integer(-3));
+    validate_eval("int(\" 42 \")", Object::Integer(42));
+    validate_eval("int(true)", Object::Integer(1));
+    validate_eval("float(2)", Object::Float(2.0));
+    validate_eval("float(\"1.5\")", Object::Float(1.5));
+    validate_eval("round(2.5)", Object::Integer(3));
+    validate_eval("round(-2.5)", Object::Integer(-3));
+    validate_eval("floor(-1.5)", Object::Integer(-2));
+    validate_eval("ceil(1.2)", Object::Integer(2));
+    validate_eval("ceil(4)", Object::Integer(4));
+
+    validate_eval_err("int(\"4x\")");
+    validate_eval_err("int(1.0 / 0.0)");
+    validate_eval_err("floor(1e300)");
+    validate_eval_err("float([1])");
+    validate_eval_err("1.5..3");
+}
+
+#[test]
+fn will_you_use_numeric_operators() {
+    validate_eval("2 ** 10", Object::Integer(1024));
+    validate_eval("2 ** 3 ** 2", Object::Integer(512));
+    validate_eval("-2 ** 2", Object::Integer(-4));
+    validate_eval("(-1) ** 9999999999", Object::Integer(-1));
+    validate_eval("0 ** 0", Object::Integer(1));
+    validate_eval("4 ** 0.5", Object::Float(2.0));
+    validate_eval("2.0 ** -1", Object::Float(0.5));
+
+    validate_eval("7 ~/ 2", Object::Integer(3));
+    validate_eval("-7 ~/ 2", Object::Integer(-4));
+    validate_eval("7 ~/ -2", Object::Integer(-4));
+    validate_eval("-8 ~/ 2", Object::Integer(-4));
+    validate_eval("7.5 ~/ 2", Object::Float(3.0));
+
+    validate_eval("12 & 10", Object::Integer(8));
+    validate_eval("12 | 10", Object::Integer(14));
+    validate_eval("12 ^ 10", Object::Integer(6));
+    validate_eval("~5", Object::Integer(-6));
+    validate_eval("1 << 4", Object::Integer(16));
+    validate_eval("-16 >> 2", Object::Integer(-4));
+    validate_eval("1 << 2 + 1", Object::Integer(8));
+    validate_eval("6 & 3 == 2", Object::Boolean(true));
+
+    let code = |input: &str| match eval_input(input) {
+        Object::Error(err) => err.code,
+        received => panic!("expected an error, received: {}", received),
+    };
+
+    assert_eq!(code("2 ** 63"), "E0104");
+    assert_eq!(code("1 ~/ 0"), "E0105");
+    assert_eq!(code("(-9223372036854775807 - 1) ~/ -1"), "E0104");
+    assert_eq!(code("1 << 63"), "E0104");
+
+    validate_eval_err("2 ** -1");
+    validate_eval_err("1 << 64");
+    validate_eval_err("1 >> -1");
+    validate_eval_err("1.5 & 1");
+    validate_eval_err("~1.5");
+    validate_eval_err("true | false");
+}
+
+#[test]
+fn will_you_short_circuit() {
+    validate_eval(
+        "let x = 0; (x != 0) && (10 / x > 1)",
+        Object::Boolean(false),
+    );
+    validate_eval("let x = 0; (x == 0) || (10 / x > 1)", Object::Boolean(true));
+    validate_eval_err("let x = 0; (x == 0) && (10 / x > 1)");
+
+    validate_eval(
+        "let calls = 0; let f = fn() { calls += 1; true }; false && f(); true || f(); calls",
+        Object::Integer(0),
+    );
+    validate_eval(
+        "let calls = 0; let f = fn() { calls += 1; true }; true && f(); false || f(); calls",
+        Object::Integer(2),
+    );
+
+    validate_eval("1 && \"a\"", Object::Boolean(true));
+    validate_eval("if (1) { 2 } else { 3 }", Object::Integer(2));
+    validate_eval("if (null) { 2 } else { 3 }", Object::Integer(3));
+    validate_eval(
+        "let xs = [1, 2]; let n = 0; while (xs?[n]) { n += 1 }; n",
+        Object::Integer(2),
+    );
+    validate_eval("match (1) { x if x => 2, _ => 3 }", Object::Integer(2));
+    validate_eval("0 || false", Object::Boolean(true));
+    validate_eval("[] && false", Object::Boolean(false));
+    validate_eval("let n = fn() {}; n() || false", Object::Boolean(false));
+    validate_eval("(!1) == (1 && false)", Object::Boolean(true));
+}
+
+#[test]
+fn will_you_compare_everything() {
+    validate_eval("[1, [2, 3]] == [1, [2, 3]]", Object::Boolean(true));
+    validate_eval("[1, 2] != [1, 2, 3]", Object::Boolean(true));
+    validate_eval("[1, 2.0] == [1.0, 2]", Object::Boolean(true));
+    validate_eval("match (2.0) { 2 => 1, _ => 0 }", Object::Integer(1));
+    validate_eval("match ([1.0]) { [1] => 1, _ => 0 }", Object::Integer(1));
+    validate_eval("let f = fn() { 1 }; f == f", Object::Boolean(true));
+    validate_eval(
+        "let f = fn() { 1 }; let g = fn() { 1 }; [f != g, [f] == [f]]",
+        Object::Array(vec![Object::Boolean(true), Object::Boolean(true)]),
+    );
+    validate_eval("len == len", Object::Boolean(true));
+    validate_eval(
+        "let f = fn() { 1 }; match (f) { 1 => 0, [] => 1, _ => 2 }",
+        Object::Integer(2),
+    );
+    validate_eval(
+        r#"{ "a": 1, "b": [2] } == { "b": [2], "a": 1 }"#,
+        Object::Boolean(true),
+    );
+    validate_eval(r#"{ "a": 1 } != { "a": 2 }"#, Object::Boolean(true));
+    validate_eval("let n = fn() {}; n() == n()", Object::Boolean(true));
+    validate_eval("let n = fn() {}; n() != 0", Object::Boolean(true));
+    validate_eval(r#""1" == 1"#, Object::Boolean(false));
+    validate_eval("true != false", Object::Boolean(true));
+
+    validate_eval(r#""apple" < "banana""#, Object::Boolean(true));
+    validate_eval(r#""b" >= "abc""#, Object::Boolean(true));
+    validate_eval(r#""abc" != "abd""#, Object::Boolean(true));
+    validate_eval("'a' < 'b'", Object::Boolean(true));
+    validate_eval("[1, 2] < [1, 3]", Object::Boolean(true));
+    validate_eval("[1, 2] < [1, 2, 0]", Object::Boolean(true));
+    validate_eval("[2] > [1, 5]", Object::Boolean(true));
+    validate_eval("[1, 2] <= [1, 2]", Object::Boolean(true));
+    validate_eval("1 < 1.5", Object::Boolean(true));
+
+    validate_eval_err(r#""a" < 1"#);
+    validate_eval_err("true < false");
+    validate_eval_err(r#"[1] < ["a"]"#);
+}
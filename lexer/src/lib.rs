@@ -4,7 +4,7 @@ pub mod token;
 mod tests;
 
 use token::{
-    kind::{LiteralKind, TokenKind},
+    kind::{LiteralKind, Radix, TokenKind},
     span::Span,
     Token, EOF_CHAR,
 };
@@ -85,12 +85,34 @@ impl Lexer {
             ')' => TokenKind::RParen,
             '[' => TokenKind::LBracket,
             ']' => TokenKind::RBracket,
-            '.' => TokenKind::Dot,
+            '.' => {
+                if self.peek() == '.' {
+                    self.advance_byte();
+                    TokenKind::DotDot
+                } else {
+                    TokenKind::Dot
+                }
+            }
+            '?' => match self.peek() {
+                '?' => {
+                    self.advance_byte();
+                    TokenKind::NullCoalesce
+                }
+                '[' => {
+                    self.advance_byte();
+                    TokenKind::OptionalIndex
+                }
+                '.' => {
+                    self.advance_byte();
+                    TokenKind::OptionalDot
+                }
+                _ => TokenKind::Illegal { ch: self.curr_ch },
+            },
             ',' => TokenKind::Comma,
             ':' => TokenKind::Colon,
             ';' => TokenKind::Semicolon,
-            '+' => TokenKind::Plus,
-            '-' => TokenKind::Minus,
+            '+' => self.with_assign(TokenKind::Plus, TokenKind::PlusAssign),
+            '-' => self.with_assign(TokenKind::Minus, TokenKind::MinusAssign),
             '!' => {
                 if self.peek() == '=' {
                     self.advance_byte();
@@ -99,21 +121,40 @@ impl Lexer {
                     TokenKind::Bang
                 }
             }
-            '*' => TokenKind::Asterisk,
+            '*' => {
+                if self.peek() == '*' {
+                    self.advance_byte();
+                    TokenKind::Power
+                } else {
+                    self.with_assign(TokenKind::Asterisk, TokenKind::AsteriskAssign)
+                }
+            }
+            '~' => {
+                if self.peek() == '/' {
+                    self.advance_byte();
+                    TokenKind::IntDivide
+                } else {
+                    TokenKind::BitNot
+                }
+            }
+            '^' => TokenKind::BitXor,
             '/' => {
                 if self.peek() == '/' {
                     self.read_line_comment()
                 } else if self.peek() == '*' {
                     self.read_block_comment()
                 } else {
-                    TokenKind::ForwardSlash
+                    self.with_assign(TokenKind::ForwardSlash, TokenKind::ForwardSlashAssign)
                 }
             }
-            '%' => TokenKind::Modulo,
+            '%' => self.with_assign(TokenKind::Modulo, TokenKind::ModuloAssign),
             '<' => {
                 if self.peek() == '=' {
                     self.advance_byte();
                     TokenKind::LessThanEqual
+                } else if self.peek() == '<' {
+                    self.advance_byte();
+                    TokenKind::ShiftLeft
                 } else {
                     TokenKind::LessThan
                 }
@@ -122,6 +163,9 @@ impl Lexer {
                 if self.peek() == '=' {
                     self.advance_byte();
                     TokenKind::GreaterThanEqual
+                } else if self.peek() == '>' {
+                    self.advance_byte();
+                    TokenKind::ShiftRight
                 } else {
                     TokenKind::GreaterThan
                 }
@@ -130,6 +174,9 @@ impl Lexer {
                 if self.peek() == '=' {
                     self.advance_byte();
                     TokenKind::Equal
+                } else if self.peek() == '>' {
+                    self.advance_byte();
+                    TokenKind::FatArrow
                 } else {
                     TokenKind::Assign
                 }
@@ -139,7 +186,7 @@ impl Lexer {
                     self.advance_byte();
                     TokenKind::Or
                 } else {
-                    TokenKind::Illegal { ch: self.curr_ch }
+                    TokenKind::BitOr
                 }
             }
             '&' => {
@@ -147,7 +194,7 @@ impl Lexer {
                     self.advance_byte();
                     TokenKind::And
                 } else {
-                    TokenKind::Illegal { ch: self.curr_ch }
+                    TokenKind::BitAnd
                 }
             }
             'a'..='z' | 'A'..='Z' | '_' => {
@@ -188,6 +235,17 @@ impl Lexer {
         }
     }
 
+    /// Picks the compound assignment variant of an operator
+    /// if it is immediately followed by '='
+    fn with_assign(&mut self, operator: TokenKind, compound: TokenKind) -> TokenKind {
+        if self.peek() == '=' {
+            self.advance_byte();
+            compound
+        } else {
+            operator
+        }
+    }
+
     fn peek(&self) -> char {
         if self.reached_end_of_input() {
             EOF_CHAR
@@ -229,25 +287,69 @@ impl Lexer {
     }
 
     fn read_int(&mut self) -> TokenKind {
+        let radix = match (self.curr_ch, self.peek()) {
+            ('0', 'x' | 'X') => Radix::Hexadecimal,
+            ('0', 'o' | 'O') => Radix::Octal,
+            ('0', 'b' | 'B') => Radix::Binary,
+            _ => return self.read_decimal(),
+        };
+
+        // skip the prefix
+        self.advance_byte();
+        self.advance_byte();
+
+        // everything alphanumeric is consumed so that malformed
+        // literals like 0xZZ end up as a single bad token
         let pos = self.position;
-        let mut dot = false;
+        while self.curr_ch.is_ascii_alphanumeric() || self.curr_ch == '_' {
+            self.advance_byte();
+        }
 
-        while self.curr_ch.is_ascii_digit() || self.curr_ch == '.' {
-            if self.curr_ch == '.' {
-                dot = true;
+        TokenKind::Literal {
+            kind: LiteralKind::Int { radix },
+            val: self.input[pos..self.position]
+                .iter()
+                .filter(|&&ch| ch != '_')
+                .collect::<String>(),
+        }
+    }
+
+    fn read_decimal(&mut self) -> TokenKind {
+        let pos = self.position;
+        let mut float = false;
+
+        loop {
+            match self.curr_ch {
+                // a dot not followed by a digit is either a range
+                // or a method call and not a part of this number
+                '.' if !self.peek().is_ascii_digit() => break,
+                '.' => float = true,
+                'e' | 'E' => {
+                    float = true;
+                    if matches!(self.peek(), '+' | '-') {
+                        self.advance_byte();
+                    }
+                }
+                ch if ch.is_ascii_alphanumeric() || ch == '_' => (),
+                _ => break,
             }
             self.advance_byte();
         }
 
-        let kind = if dot {
+        let kind = if float {
             LiteralKind::Float
         } else {
-            LiteralKind::Int
+            LiteralKind::Int {
+                radix: Radix::Decimal,
+            }
         };
 
         TokenKind::Literal {
             kind,
-            val: self.input[pos..self.position].iter().collect::<String>(),
+            val: self.input[pos..self.position]
+                .iter()
+                .filter(|&&ch| ch != '_')
+                .collect::<String>(),
         }
     }
 
@@ -256,18 +358,22 @@ impl Lexer {
 
         let mut estr = String::new();
         let mut terminated = true;
+        let mut bad_escape = None;
 
         while self.curr_ch != '"' {
             if self.curr_ch == '\\' {
-                // if a backslash is found we skip it
-                // and read the next character as is
+                match self.read_escape() {
+                    Ok(ch) => estr.push(ch),
+                    Err(escape) => {
+                        bad_escape.get_or_insert(escape);
+                    }
+                }
+            } else {
+                estr.push(self.curr_ch);
                 self.advance_byte();
             }
 
-            estr.push(self.curr_ch);
-            self.advance_byte();
-
-            if self.reached_end_of_input() {
+            if self.reached_end_of_input() && self.curr_ch != '"' {
                 terminated = false;
                 break;
             }
@@ -277,9 +383,12 @@ impl Lexer {
             self.advance_byte(); // skip the closing "
         }
 
-        TokenKind::Literal {
-            kind: LiteralKind::Str { terminated },
-            val: estr,
+        match bad_escape {
+            Some((seq, span)) => TokenKind::BadEscape { seq, span },
+            None => TokenKind::Literal {
+                kind: LiteralKind::Str { terminated },
+                val: estr,
+            },
         }
     }
 
@@ -287,8 +396,20 @@ impl Lexer {
         self.advance_byte();
 
         let mut val = String::new();
-        val.push(self.curr_ch);
-        self.advance_byte();
+        if self.curr_ch == '\\' {
+            match self.read_escape() {
+                Ok(ch) => val.push(ch),
+                Err((seq, span)) => {
+                    if self.curr_ch == '\'' {
+                        self.advance_byte();
+                    }
+                    return TokenKind::BadEscape { seq, span };
+                }
+            }
+        } else {
+            val.push(self.curr_ch);
+            self.advance_byte();
+        }
 
         let terminated = self.curr_ch == '\'';
         if terminated {
@@ -301,6 +422,73 @@ impl Lexer {
         }
     }
 
+    /// Reads an escape sequence starting at the backslash under the cursor.
+    /// On failure the raw sequence and its span are returned so that
+    /// the parser can point at it
+    fn read_escape(&mut self) -> Result<char, (String, Span)> {
+        let start = self.position;
+        self.advance_byte(); // skip the backslash
+
+        let escaped = match self.curr_ch {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            '\\' => Some('\\'),
+            '"' => Some('"'),
+            '\'' => Some('\''),
+            'u' => return self.read_unicode_escape(start),
+            _ => None,
+        };
+
+        if self.position < self.input.len() {
+            self.advance_byte();
+        }
+
+        escaped.ok_or_else(|| self.escape_err(start))
+    }
+
+    /// Reads the `{XXXX}` part of a `\u{XXXX}` escape.
+    /// Accepts one to six hex digits that form a valid code point
+    fn read_unicode_escape(&mut self, start: usize) -> Result<char, (String, Span)> {
+        self.advance_byte(); // skip the u
+
+        if self.curr_ch != '{' {
+            return Err(self.escape_err(start));
+        }
+        self.advance_byte();
+
+        let digits_pos = self.position;
+        while self.curr_ch.is_ascii_hexdigit() {
+            self.advance_byte();
+        }
+        let digits = self.input[digits_pos..self.position]
+            .iter()
+            .collect::<String>();
+
+        if self.curr_ch != '}' {
+            return Err(self.escape_err(start));
+        }
+        self.advance_byte();
+
+        if digits.is_empty() || digits.len() > 6 {
+            return Err(self.escape_err(start));
+        }
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.escape_err(start))
+    }
+
+    fn escape_err(&self, start: usize) -> (String, Span) {
+        let end = self.position.min(self.input.len());
+        (
+            self.input[start..end].iter().collect::<String>(),
+            self.make_span(start),
+        )
+    }
+
     fn read_line_comment(&mut self) -> TokenKind {
         // skip the '//' which denotes start of a comment
         self.advance_byte();
@@ -1,7 +1,10 @@
 use std::fs;
 
 use crate::{
-    token::kind::{LiteralKind, TokenKind},
+    token::{
+        kind::{LiteralKind, Radix, TokenKind},
+        span::Span,
+    },
     Lexer,
 };
 
@@ -62,7 +65,9 @@ fn will_you_lex_some_code() {
         },
         TokenKind::Assign,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("5"),
         },
         TokenKind::Semicolon,
@@ -72,7 +77,9 @@ fn will_you_lex_some_code() {
         },
         TokenKind::Assign,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("10"),
         },
         TokenKind::Semicolon,
@@ -191,7 +198,9 @@ fn will_you_lex_more_code() {
         },
         TokenKind::Assign,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("5"),
         },
         TokenKind::Semicolon,
@@ -201,7 +210,9 @@ fn will_you_lex_more_code() {
         },
         TokenKind::Assign,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("10"),
         },
         TokenKind::Semicolon,
@@ -245,35 +256,47 @@ fn will_you_lex_more_code() {
         TokenKind::Minus,
         TokenKind::ForwardSlash,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("5"),
         },
         TokenKind::Asterisk,
         TokenKind::Semicolon,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("5"),
         },
         TokenKind::LessThan,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("10"),
         },
         TokenKind::GreaterThan,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("5"),
         },
         TokenKind::Semicolon,
         TokenKind::If,
         TokenKind::LParen,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("5"),
         },
         TokenKind::LessThan,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("10"),
         },
         TokenKind::RParen,
@@ -289,32 +312,44 @@ fn will_you_lex_more_code() {
         TokenKind::Semicolon,
         TokenKind::RCurly,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("5"),
         },
         TokenKind::LessThanEqual,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("10"),
         },
         TokenKind::Semicolon,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("10"),
         },
         TokenKind::Equal,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("10"),
         },
         TokenKind::Semicolon,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("10"),
         },
         TokenKind::NotEqual,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: String::from("9"),
         },
         TokenKind::Semicolon,
@@ -326,64 +361,48 @@ fn will_you_lex_more_code() {
 
 #[test]
 fn will_you_lex_from_a_file() {
-    let contents = fs::read_to_string("../docs/examples/basic.lace").unwrap();
+    let contents = fs::read_to_string("../docs/examples/try.lace").unwrap();
     let input = contents.as_str();
 
+    let string = |val: &str| TokenKind::Literal {
+        kind: LiteralKind::Str { terminated: true },
+        val: val.into(),
+    };
+
     let tokens = vec![
-        TokenKind::Function,
-        TokenKind::Ident {
-            label: "main".into(),
-        },
-        TokenKind::LParen,
-        TokenKind::RParen,
-        TokenKind::LCurly,
         TokenKind::Let,
         TokenKind::Ident {
-            label: "num1".into(),
+            label: "sentence".into(),
         },
         TokenKind::Assign,
-        TokenKind::Literal {
-            kind: LiteralKind::Int,
-            val: String::from("69"),
-        },
+        string("May the lace code run"),
         TokenKind::Semicolon,
         TokenKind::Let,
         TokenKind::Ident {
-            label: "num2".into(),
+            label: "words".into(),
         },
         TokenKind::Assign,
-        TokenKind::Literal {
-            kind: LiteralKind::Int,
-            val: String::from("420"),
+        TokenKind::Ident {
+            label: "split".into(),
         },
+        TokenKind::LParen,
+        TokenKind::Ident {
+            label: "sentence".into(),
+        },
+        TokenKind::Comma,
+        string(" "),
+        TokenKind::RParen,
         TokenKind::Semicolon,
-        TokenKind::Let,
         TokenKind::Ident {
-            label: "bigger_of_the_2".into(),
+            label: "write".into(),
         },
-        TokenKind::Assign,
-        TokenKind::Function,
         TokenKind::LParen,
-        TokenKind::Ident { label: "x".into() },
-        TokenKind::Comma,
-        TokenKind::Ident { label: "y".into() },
+        TokenKind::Ident {
+            label: "words".into(),
+        },
         TokenKind::RParen,
-        TokenKind::LCurly,
-        TokenKind::If,
-        TokenKind::Ident { label: "x".into() },
-        TokenKind::GreaterThan,
-        TokenKind::Ident { label: "y".into() },
-        TokenKind::LCurly,
-        TokenKind::Ident { label: "x".into() },
-        TokenKind::RCurly,
-        TokenKind::Else,
-        TokenKind::LCurly,
-        TokenKind::Ident { label: "y".into() },
-        TokenKind::RCurly,
-        TokenKind::RCurly,
         TokenKind::Semicolon,
-        TokenKind::RCurly,
-        TokenKind::Eof,
+        TokenKind::Let,
     ];
 
     validate_tokens(input, tokens)
@@ -427,6 +446,256 @@ fn will_you_escape() {
     validate_tokens(input, tokens)
 }
 
+#[test]
+fn will_you_escape_sequences() {
+    let input = r#"
+        "tab\tnew\nret\rnul\0";
+        '\n'; '\''; '\\';
+        "smile \u{1F600} \'quoted\'";
+    "#;
+
+    let tokens = vec![
+        TokenKind::Literal {
+            kind: LiteralKind::Str { terminated: true },
+            val: String::from("tab\tnew\nret\rnul\0"),
+        },
+        TokenKind::Semicolon,
+        TokenKind::Literal {
+            kind: LiteralKind::Char { terminated: true },
+            val: String::from("\n"),
+        },
+        TokenKind::Semicolon,
+        TokenKind::Literal {
+            kind: LiteralKind::Char { terminated: true },
+            val: String::from("'"),
+        },
+        TokenKind::Semicolon,
+        TokenKind::Literal {
+            kind: LiteralKind::Char { terminated: true },
+            val: String::from("\\"),
+        },
+        TokenKind::Semicolon,
+        TokenKind::Literal {
+            kind: LiteralKind::Str { terminated: true },
+            val: String::from("smile \u{1F600} 'quoted'"),
+        },
+        TokenKind::Semicolon,
+        TokenKind::Eof,
+    ];
+
+    validate_tokens(input, tokens)
+}
+
+#[test]
+fn detect_bad_escape() {
+    let input = r#"let s = "bad \q escape"; '\u{110000}'; "\u{zz}""#;
+
+    let tokens = vec![
+        TokenKind::Let,
+        TokenKind::Ident { label: "s".into() },
+        TokenKind::Assign,
+        TokenKind::BadEscape {
+            seq: r"\q".into(),
+            span: Span {
+                start_line: 1,
+                end_line: 1,
+                start_col: 13,
+                end_col: 15,
+            },
+        },
+        TokenKind::Semicolon,
+        TokenKind::BadEscape {
+            seq: r"\u{110000}".into(),
+            span: Span {
+                start_line: 1,
+                end_line: 1,
+                start_col: 26,
+                end_col: 36,
+            },
+        },
+        TokenKind::Semicolon,
+        TokenKind::BadEscape {
+            seq: r"\u{".into(),
+            span: Span {
+                start_line: 1,
+                end_line: 1,
+                start_col: 40,
+                end_col: 43,
+            },
+        },
+        TokenKind::Eof,
+    ];
+
+    validate_tokens(input, tokens)
+}
+
+#[test]
+fn will_you_lex_assignments() {
+    let input = "x = 1; x += 1; x -= 1; x *= 1; x /= 1; x %= 1; x / 1";
+
+    let one = TokenKind::Literal {
+        kind: LiteralKind::Int {
+            radix: Radix::Decimal,
+        },
+        val: "1".into(),
+    };
+
+    let mut tokens = Vec::new();
+    for operator in [
+        TokenKind::Assign,
+        TokenKind::PlusAssign,
+        TokenKind::MinusAssign,
+        TokenKind::AsteriskAssign,
+        TokenKind::ForwardSlashAssign,
+        TokenKind::ModuloAssign,
+    ] {
+        tokens.extend([
+            TokenKind::Ident { label: "x".into() },
+            operator,
+            one.clone(),
+            TokenKind::Semicolon,
+        ]);
+    }
+    tokens.extend([
+        TokenKind::Ident { label: "x".into() },
+        TokenKind::ForwardSlash,
+        one,
+        TokenKind::Eof,
+    ]);
+
+    validate_tokens(input, tokens)
+}
+
+#[test]
+fn will_you_lex_numeric_operators() {
+    let input = "a ** b ~/ c & d | e ^ ~f << g >> h && i || j *= k";
+
+    let tokens = vec![
+        TokenKind::Ident { label: "a".into() },
+        TokenKind::Power,
+        TokenKind::Ident { label: "b".into() },
+        TokenKind::IntDivide,
+        TokenKind::Ident { label: "c".into() },
+        TokenKind::BitAnd,
+        TokenKind::Ident { label: "d".into() },
+        TokenKind::BitOr,
+        TokenKind::Ident { label: "e".into() },
+        TokenKind::BitXor,
+        TokenKind::BitNot,
+        TokenKind::Ident { label: "f".into() },
+        TokenKind::ShiftLeft,
+        TokenKind::Ident { label: "g".into() },
+        TokenKind::ShiftRight,
+        TokenKind::Ident { label: "h".into() },
+        TokenKind::And,
+        TokenKind::Ident { label: "i".into() },
+        TokenKind::Or,
+        TokenKind::Ident { label: "j".into() },
+        TokenKind::AsteriskAssign,
+        TokenKind::Ident { label: "k".into() },
+        TokenKind::Eof,
+    ];
+
+    validate_tokens(input, tokens)
+}
+
+#[test]
+fn will_you_lex_optional_chaining() {
+    let input = "a?.b ?? c?[null] ? d";
+
+    let tokens = vec![
+        TokenKind::Ident { label: "a".into() },
+        TokenKind::OptionalDot,
+        TokenKind::Ident { label: "b".into() },
+        TokenKind::NullCoalesce,
+        TokenKind::Ident { label: "c".into() },
+        TokenKind::OptionalIndex,
+        TokenKind::Null,
+        TokenKind::RBracket,
+        TokenKind::Illegal { ch: '?' },
+        TokenKind::Ident { label: "d".into() },
+        TokenKind::Eof,
+    ];
+
+    validate_tokens(input, tokens)
+}
+
+#[test]
+fn will_you_lex_numbers() {
+    let input = "0xFF_ff 0o17 0b1010 1_000_000 1.5e-3 2E10 1.2.3 0xZZ 0..5 7.abs";
+
+    let tokens = vec![
+        TokenKind::Literal {
+            kind: LiteralKind::Int {
+                radix: Radix::Hexadecimal,
+            },
+            val: "FFff".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Int {
+                radix: Radix::Octal,
+            },
+            val: "17".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Int {
+                radix: Radix::Binary,
+            },
+            val: "1010".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
+            val: "1000000".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Float,
+            val: "1.5e-3".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Float,
+            val: "2E10".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Float,
+            val: "1.2.3".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Int {
+                radix: Radix::Hexadecimal,
+            },
+            val: "ZZ".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
+            val: "0".into(),
+        },
+        TokenKind::DotDot,
+        TokenKind::Literal {
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
+            val: "5".into(),
+        },
+        TokenKind::Literal {
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
+            val: "7".into(),
+        },
+        TokenKind::Dot,
+        TokenKind::Ident {
+            label: "abs".into(),
+        },
+        TokenKind::Eof,
+    ];
+
+    validate_tokens(input, tokens)
+}
+
 #[test]
 fn will_you_lex_a_comment() {
     let input = r#"
@@ -451,7 +720,9 @@ block comment */
         },
         TokenKind::Assign,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: "0".into(),
         },
         TokenKind::Semicolon,
@@ -467,7 +738,9 @@ block comment */
         },
         TokenKind::Plus,
         TokenKind::Literal {
-            kind: LiteralKind::Int,
+            kind: LiteralKind::Int {
+                radix: Radix::Decimal,
+            },
             val: "1".into(),
         },
         TokenKind::Semicolon,
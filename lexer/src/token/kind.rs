@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use super::span::Span;
+
 /// Represents the parsed token type.
 /// In case of a string or integer literal,
 /// we store the datatype and value
@@ -19,6 +21,18 @@ pub enum TokenKind {
     // Operators
     /// =
     Assign,
+    /// =>
+    FatArrow,
+    /// +=
+    PlusAssign,
+    /// -=
+    MinusAssign,
+    /// *=
+    AsteriskAssign,
+    /// /=
+    ForwardSlashAssign,
+    /// %=
+    ModuloAssign,
 
     // Arithmetic
     /// +
@@ -31,6 +45,10 @@ pub enum TokenKind {
     Modulo,
     /// *
     Asterisk,
+    /// **
+    Power,
+    /// ~/
+    IntDivide,
 
     // Logical
     /// !
@@ -39,6 +57,22 @@ pub enum TokenKind {
     And,
     /// ||
     Or,
+    /// ??
+    NullCoalesce,
+
+    // Bitwise
+    /// &
+    BitAnd,
+    /// |
+    BitOr,
+    /// ^
+    BitXor,
+    /// ~
+    BitNot,
+    /// <<
+    ShiftLeft,
+    /// >>
+    ShiftRight,
 
     // Relational
     /// ==
@@ -57,6 +91,10 @@ pub enum TokenKind {
     // Delimiters
     /// .
     Dot,
+    /// ..
+    DotDot,
+    /// ?.
+    OptionalDot,
     /// ,
     Comma,
     /// ;
@@ -73,6 +111,8 @@ pub enum TokenKind {
     RCurly,
     /// [
     LBracket,
+    /// ?[
+    OptionalIndex,
     /// ]
     RBracket,
 
@@ -84,7 +124,17 @@ pub enum TokenKind {
     Else,
     True,
     False,
+    Null,
     Source,
+    Import,
+    Export,
+    As,
+    While,
+    For,
+    In,
+    Break,
+    Continue,
+    Match,
 
     // Comments
     /// //
@@ -97,6 +147,14 @@ pub enum TokenKind {
         terminated: bool,
     },
 
+    /// String or character literal containing an escape
+    /// sequence that couldn't be resolved.
+    /// Holds the offending sequence along with its position
+    BadEscape {
+        seq: String,
+        span: Span,
+    },
+
     /// Unknown or unrecognizable tokens.
     /// Includes emojis and other non ASCII characters.
     Illegal {
@@ -113,10 +171,24 @@ impl Display for TokenKind {
             TokenKind::Ident { label } => write!(f, "Ident({label})"),
             TokenKind::Literal { kind, val } => write!(f, "{kind} Literal({val})"),
             TokenKind::Assign => write!(f, "Assign"),
+            TokenKind::FatArrow => write!(f, "FatArrow"),
+            TokenKind::PlusAssign => write!(f, "PlusAssign"),
+            TokenKind::MinusAssign => write!(f, "MinusAssign"),
+            TokenKind::AsteriskAssign => write!(f, "AsteriskAssign"),
+            TokenKind::ForwardSlashAssign => write!(f, "ForwardSlashAssign"),
+            TokenKind::ModuloAssign => write!(f, "ModuloAssign"),
             TokenKind::Bang => write!(f, "Bang"),
             TokenKind::Minus => write!(f, "Minus"),
             TokenKind::Plus => write!(f, "Plus"),
             TokenKind::Asterisk => write!(f, "Asterisk"),
+            TokenKind::Power => write!(f, "Power"),
+            TokenKind::IntDivide => write!(f, "IntDivide"),
+            TokenKind::BitAnd => write!(f, "BitAnd"),
+            TokenKind::BitOr => write!(f, "BitOr"),
+            TokenKind::BitXor => write!(f, "BitXor"),
+            TokenKind::BitNot => write!(f, "BitNot"),
+            TokenKind::ShiftLeft => write!(f, "ShiftLeft"),
+            TokenKind::ShiftRight => write!(f, "ShiftRight"),
             TokenKind::ForwardSlash => write!(f, "ForwardSlash"),
             TokenKind::Modulo => write!(f, "Modulo"),
             TokenKind::Equal => write!(f, "Equal"),
@@ -127,7 +199,10 @@ impl Display for TokenKind {
             TokenKind::GreaterThanEqual => write!(f, "GreaterThanEqual"),
             TokenKind::Or => write!(f, "Or"),
             TokenKind::And => write!(f, "And"),
+            TokenKind::NullCoalesce => write!(f, "NullCoalesce"),
             TokenKind::Dot => write!(f, "Dot"),
+            TokenKind::DotDot => write!(f, "DotDot"),
+            TokenKind::OptionalDot => write!(f, "OptionalDot"),
             TokenKind::Comma => write!(f, "Comma"),
             TokenKind::Colon => write!(f, "Colon"),
             TokenKind::Semicolon => write!(f, "Semicolon"),
@@ -136,20 +211,32 @@ impl Display for TokenKind {
             TokenKind::LCurly => write!(f, "LSquirly"),
             TokenKind::RCurly => write!(f, "RSquirly"),
             TokenKind::LBracket => write!(f, "LBracket"),
+            TokenKind::OptionalIndex => write!(f, "OptionalIndex"),
             TokenKind::RBracket => write!(f, "RBracket"),
             TokenKind::Let => write!(f, "Let"),
             TokenKind::Function => write!(f, "Function"),
             TokenKind::Source => write!(f, "Source"),
+            TokenKind::Import => write!(f, "Import"),
+            TokenKind::Export => write!(f, "Export"),
+            TokenKind::As => write!(f, "As"),
             TokenKind::Return => write!(f, "Return"),
+            TokenKind::While => write!(f, "While"),
+            TokenKind::For => write!(f, "For"),
+            TokenKind::In => write!(f, "In"),
+            TokenKind::Break => write!(f, "Break"),
+            TokenKind::Continue => write!(f, "Continue"),
+            TokenKind::Match => write!(f, "Match"),
             TokenKind::If => write!(f, "If"),
             TokenKind::Else => write!(f, "Else"),
             TokenKind::True => write!(f, "True"),
             TokenKind::False => write!(f, "False"),
+            TokenKind::Null => write!(f, "Null"),
             TokenKind::LineComment { content } => write!(f, "LineComment {content}"),
             TokenKind::BlockComment {
                 content,
                 terminated: _,
             } => write!(f, "BlockComment {content}"),
+            TokenKind::BadEscape { seq, span: _ } => write!(f, "BadEscape {seq}"),
             TokenKind::Eof => write!(f, "Eof"),
             TokenKind::Illegal { ch } => write!(f, "{ch}"),
         }
@@ -157,6 +244,20 @@ impl Display for TokenKind {
 }
 
 impl TokenKind {
+    /// Arithmetic operator applied by a compound assignment
+    pub fn compound_operator(&self) -> Option<TokenKind> {
+        let operator = match self {
+            TokenKind::PlusAssign => TokenKind::Plus,
+            TokenKind::MinusAssign => TokenKind::Minus,
+            TokenKind::AsteriskAssign => TokenKind::Asterisk,
+            TokenKind::ForwardSlashAssign => TokenKind::ForwardSlash,
+            TokenKind::ModuloAssign => TokenKind::Modulo,
+            _ => return None,
+        };
+
+        Some(operator)
+    }
+
     pub fn try_keyword(label: &str) -> Option<TokenKind> {
         let keyword = match label {
             "fn" => TokenKind::Function,
@@ -165,13 +266,93 @@ impl TokenKind {
             "else" => TokenKind::Else,
             "true" => TokenKind::True,
             "false" => TokenKind::False,
+            "null" => TokenKind::Null,
             "return" => TokenKind::Return,
             "source" => TokenKind::Source,
+            "import" => TokenKind::Import,
+            "export" => TokenKind::Export,
+            "as" => TokenKind::As,
+            "while" => TokenKind::While,
+            "for" => TokenKind::For,
+            "in" => TokenKind::In,
+            "break" => TokenKind::Break,
+            "continue" => TokenKind::Continue,
+            "match" => TokenKind::Match,
             _ => return None,
         };
 
         Some(keyword)
     }
+
+    /// How keywords, operators and delimiters are written in the source
+    pub fn text(&self) -> Option<&'static str> {
+        let text = match self {
+            TokenKind::Function => "fn",
+            TokenKind::Let => "let",
+            TokenKind::If => "if",
+            TokenKind::Else => "else",
+            TokenKind::True => "true",
+            TokenKind::False => "false",
+            TokenKind::Null => "null",
+            TokenKind::Return => "return",
+            TokenKind::Source => "source",
+            TokenKind::Import => "import",
+            TokenKind::Export => "export",
+            TokenKind::As => "as",
+            TokenKind::While => "while",
+            TokenKind::For => "for",
+            TokenKind::In => "in",
+            TokenKind::Break => "break",
+            TokenKind::Continue => "continue",
+            TokenKind::Match => "match",
+            TokenKind::Assign => "=",
+            TokenKind::FatArrow => "=>",
+            TokenKind::PlusAssign => "+=",
+            TokenKind::MinusAssign => "-=",
+            TokenKind::AsteriskAssign => "*=",
+            TokenKind::ForwardSlashAssign => "/=",
+            TokenKind::ModuloAssign => "%=",
+            TokenKind::Plus => "+",
+            TokenKind::Minus => "-",
+            TokenKind::ForwardSlash => "/",
+            TokenKind::Modulo => "%",
+            TokenKind::Asterisk => "*",
+            TokenKind::Power => "**",
+            TokenKind::IntDivide => "~/",
+            TokenKind::BitAnd => "&",
+            TokenKind::BitOr => "|",
+            TokenKind::BitXor => "^",
+            TokenKind::BitNot => "~",
+            TokenKind::ShiftLeft => "<<",
+            TokenKind::ShiftRight => ">>",
+            TokenKind::Bang => "!",
+            TokenKind::And => "&&",
+            TokenKind::Or => "||",
+            TokenKind::NullCoalesce => "??",
+            TokenKind::Equal => "==",
+            TokenKind::NotEqual => "!=",
+            TokenKind::LessThan => "<",
+            TokenKind::GreaterThan => ">",
+            TokenKind::LessThanEqual => "<=",
+            TokenKind::GreaterThanEqual => ">=",
+            TokenKind::Dot => ".",
+            TokenKind::DotDot => "..",
+            TokenKind::OptionalDot => "?.",
+            TokenKind::Comma => ",",
+            TokenKind::Semicolon => ";",
+            TokenKind::Colon => ":",
+            TokenKind::LParen => "(",
+            TokenKind::RParen => ")",
+            TokenKind::LCurly => "{",
+            TokenKind::RCurly => "}",
+            TokenKind::LBracket => "[",
+            TokenKind::OptionalIndex => "?[",
+            TokenKind::RBracket => "]",
+            _ => return None,
+        };
+
+        Some(text)
+    }
 }
 
 /// Valid datatypes.
@@ -180,7 +361,7 @@ impl TokenKind {
 #[derive(Debug, PartialEq, Clone)]
 pub enum LiteralKind {
     /// 64 bit signed integer
-    Int,
+    Int { radix: Radix },
     /// 64 bit signed floating point number
     Float,
     /// Characters
@@ -192,7 +373,10 @@ pub enum LiteralKind {
 impl Display for LiteralKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            LiteralKind::Int => write!(f, "Int"),
+            LiteralKind::Int { radix } => match radix {
+                Radix::Decimal => write!(f, "Int"),
+                _ => write!(f, "{radix} Int"),
+            },
             LiteralKind::Float => write!(f, "Float"),
             LiteralKind::Char { terminated } => match terminated {
                 true => write!(f, "Char"),
@@ -205,3 +389,26 @@ impl Display for LiteralKind {
         }
     }
 }
+
+/// Base in which an integer literal was written
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Radix {
+    /// 0b
+    Binary = 2,
+    /// 0o
+    Octal = 8,
+    Decimal = 10,
+    /// 0x
+    Hexadecimal = 16,
+}
+
+impl Display for Radix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Radix::Binary => write!(f, "Binary"),
+            Radix::Octal => write!(f, "Octal"),
+            Radix::Decimal => write!(f, "Decimal"),
+            Radix::Hexadecimal => write!(f, "Hex"),
+        }
+    }
+}
@@ -41,19 +41,6 @@ impl Token {
     pub fn reached_eof(&self) -> bool {
         self.kind == TokenKind::Eof
     }
-
-    pub fn is_actually_legal(&self) -> bool {
-        matches!(
-            self.kind,
-            TokenKind::LBracket
-                | TokenKind::RBracket
-                | TokenKind::LParen
-                | TokenKind::RParen
-                | TokenKind::LCurly
-                | TokenKind::RCurly
-                | TokenKind::Semicolon
-        )
-    }
 }
 
 pub fn dummy_token(kind: TokenKind) -> Token {
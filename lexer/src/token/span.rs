@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Span {
     pub start_line: usize,
     pub end_line: usize,
@@ -30,6 +30,18 @@ impl Display for Span {
     }
 }
 
+impl Span {
+    /// Span starting where this one starts and ending where the other ends
+    pub fn to(&self, end: &Span) -> Span {
+        Span {
+            start_line: self.start_line,
+            end_line: end.end_line,
+            start_col: self.start_col,
+            end_col: end.end_col,
+        }
+    }
+}
+
 pub fn dummy_span() -> Span {
     Span {
         start_line: 0,
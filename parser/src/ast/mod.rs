@@ -1,12 +1,26 @@
+/// Compares nodes field by field, leaving out their span.
+/// Like tokens, nodes are equal regardless of where they were found
+macro_rules! eq_without_span {
+    ($node:ty, $($field:ident),+) => {
+        impl PartialEq for $node {
+            fn eq(&self, other: &Self) -> bool {
+                $(self.$field == other.$field)&&+
+            }
+        }
+    };
+}
+
 pub mod nodes;
+pub mod pattern;
 pub mod statement;
 
 use std::fmt::Display;
 
-use lace_lexer::token::{dummy_token, kind::TokenKind, span::dummy_span, Token};
+use lace_lexer::token::{dummy_token, kind::TokenKind, span::Span, Token};
 use nodes::{
-    ArrayLiteral, ConditionalOperator, FunctionCall, FunctionLiteral, HashLiteral, IdentNode,
-    IndexAccess, InfixOperator, PrefixOperator, PrimitiveNode,
+    ArrayLiteral, Assignment, ConditionalOperator, ForLoop, FunctionCall, FunctionLiteral,
+    HashLiteral, IdentNode, IndexAccess, InfixOperator, LiteralNode, MatchExpression, MemberAccess,
+    PrefixOperator, WhileLoop,
 };
 use statement::Statement;
 
@@ -15,7 +29,7 @@ use crate::{
     Parser, ParserResult,
 };
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Program {
     pub statements: Vec<Statement>,
 }
@@ -35,7 +49,7 @@ impl Display for Program {
 #[derive(PartialEq, Debug, Clone)]
 pub enum Expression {
     Identifier(IdentNode),
-    Primitive(PrimitiveNode),
+    Primitive(LiteralNode),
     Unary(PrefixOperator),
     Binary(InfixOperator),
     Conditional(ConditionalOperator),
@@ -44,6 +58,11 @@ pub enum Expression {
     Array(ArrayLiteral),
     ArrIndex(IndexAccess),
     HashMapLiteral(HashLiteral),
+    While(WhileLoop),
+    For(ForLoop),
+    Assign(Assignment),
+    Member(MemberAccess),
+    Match(MatchExpression),
 }
 
 impl Display for Expression {
@@ -59,18 +78,46 @@ impl Display for Expression {
             Expression::Array(x) => write!(f, "{}", x),
             Expression::ArrIndex(x) => write!(f, "{}", x),
             Expression::HashMapLiteral(x) => write!(f, "{}", x),
+            Expression::While(x) => write!(f, "{}", x),
+            Expression::For(x) => write!(f, "{}", x),
+            Expression::Assign(x) => write!(f, "{}", x),
+            Expression::Member(x) => write!(f, "{}", x),
+            Expression::Match(x) => write!(f, "{}", x),
         }
     }
 }
 
 impl Expression {
+    /// Where the expression starts and ends in the input
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Identifier(x) => x.span(),
+            Expression::Primitive(x) => x.span.clone(),
+            Expression::Unary(x) => x.span.clone(),
+            Expression::Binary(x) => x.span.clone(),
+            Expression::Conditional(x) => x.span.clone(),
+            Expression::FunctionDef(x) => x.span.clone(),
+            Expression::FunctionCall(x) => x.span.clone(),
+            Expression::Array(x) => x.span.clone(),
+            Expression::ArrIndex(x) => x.span.clone(),
+            Expression::HashMapLiteral(x) => x.span.clone(),
+            Expression::While(x) => x.span.clone(),
+            Expression::For(x) => x.span.clone(),
+            Expression::Assign(x) => x.span.clone(),
+            Expression::Member(x) => x.span.clone(),
+            Expression::Match(x) => x.span.clone(),
+        }
+    }
+
     pub fn parse(parser: &mut Parser, precedence: Precedence) -> ParserResult<Expression> {
         let mut left_expr = match &parser.curr_token.kind {
             TokenKind::Ident { label: _ } => IdentNode::parse(parser).map(Expression::Identifier),
-            TokenKind::Literal { kind: _, val: _ } | TokenKind::False | TokenKind::True => {
-                PrimitiveNode::parse(parser).map(Expression::Primitive)
-            }
-            TokenKind::Bang | TokenKind::Minus => {
+            TokenKind::Literal { kind: _, val: _ }
+            | TokenKind::BadEscape { seq: _, span: _ }
+            | TokenKind::False
+            | TokenKind::True
+            | TokenKind::Null => LiteralNode::parse(parser).map(Expression::Primitive),
+            TokenKind::Bang | TokenKind::Minus | TokenKind::BitNot => {
                 PrefixOperator::parse(parser).map(Expression::Unary)
             }
             TokenKind::LParen => Self::parse_grouped_expr(parser),
@@ -78,6 +125,9 @@ impl Expression {
             TokenKind::Function => FunctionLiteral::parse(parser).map(Expression::FunctionDef),
             TokenKind::LBracket => ArrayLiteral::parse(parser).map(Expression::Array),
             TokenKind::LCurly => HashLiteral::parse(parser).map(Expression::HashMapLiteral),
+            TokenKind::While => WhileLoop::parse(parser).map(Expression::While),
+            TokenKind::For => ForLoop::parse(parser).map(Expression::For),
+            TokenKind::Match => MatchExpression::parse(parser).map(Expression::Match),
             _ => {
                 return Err(Box::new(NoPrefixParser {
                     token: parser.curr_token.clone(),
@@ -100,21 +150,45 @@ impl Expression {
                 | TokenKind::GreaterThanEqual
                 | TokenKind::And
                 | TokenKind::Or
-                | TokenKind::Modulo => {
+                | TokenKind::Modulo
+                | TokenKind::Power
+                | TokenKind::IntDivide
+                | TokenKind::BitAnd
+                | TokenKind::BitOr
+                | TokenKind::BitXor
+                | TokenKind::ShiftLeft
+                | TokenKind::ShiftRight
+                | TokenKind::NullCoalesce
+                | TokenKind::DotDot => {
                     parser.next_token();
                     left_expr = Expression::Binary(InfixOperator::parse(parser, left_expr)?);
                 }
 
+                TokenKind::Assign
+                | TokenKind::PlusAssign
+                | TokenKind::MinusAssign
+                | TokenKind::AsteriskAssign
+                | TokenKind::ForwardSlashAssign
+                | TokenKind::ModuloAssign => {
+                    parser.next_token();
+                    left_expr = Expression::Assign(Assignment::parse(parser, left_expr)?);
+                }
+
                 TokenKind::LParen => {
                     parser.next_token();
                     left_expr = Expression::FunctionCall(FunctionCall::parse(parser, left_expr)?);
                 }
 
-                TokenKind::LBracket => {
+                TokenKind::LBracket | TokenKind::OptionalIndex => {
                     parser.next_token();
                     left_expr = Expression::ArrIndex(IndexAccess::parse(parser, left_expr)?);
                 }
 
+                TokenKind::Dot | TokenKind::OptionalDot => {
+                    parser.next_token();
+                    left_expr = Expression::Member(MemberAccess::parse(parser, left_expr)?);
+                }
+
                 _ => return Ok(left_expr),
             }
         }
@@ -123,18 +197,17 @@ impl Expression {
     }
 
     fn parse_grouped_expr(parser: &mut Parser) -> ParserResult<Expression> {
-        let start = parser.lexer.curr_pos();
+        let start = parser.curr_token.span.clone();
         parser.next_token();
 
         let expr = Expression::parse(parser, Precedence::Lowest);
 
-        if parser.expect_peek(&dummy_token(TokenKind::RParen)) {
+        if parser.accept_peek(&dummy_token(TokenKind::RParen)) {
             expr
         } else {
             Err(Box::new(ExprError::new(
                 start,
-                Some(parser.lexer.curr_pos()),
-                parser.lexer.curr_col(),
+                Some(parser.curr_token.span.clone()),
             )))
         }
     }
@@ -147,21 +220,19 @@ impl Expression {
         }
 
         parser.next_token();
+        let start = parser.curr_token.span.clone();
         expr_list.push(Expression::parse(parser, Precedence::Lowest)?);
-        let mut start = dummy_span();
 
         while parser.peek_token_is(&TokenKind::Comma) {
             parser.next_token();
-            start = parser.lexer.curr_pos();
             parser.next_token();
             expr_list.push(Expression::parse(parser, Precedence::Lowest)?);
         }
 
-        if !parser.expect_peek(end) {
+        if !parser.accept_peek(end) {
             Err(Box::new(ExprError::new(
                 start,
-                Some(parser.lexer.curr_pos()),
-                parser.lexer.curr_col(),
+                Some(parser.curr_token.span.clone()),
             )))
         } else {
             Ok(expr_list)
@@ -169,33 +240,113 @@ impl Expression {
     }
 }
 
+/// How tightly operators bind, loosest first
 #[derive(PartialEq, PartialOrd, Clone)]
 pub enum Precedence {
     Lowest = 0,
-    Equality = 1,
-    Comparative = 2,
-    Additive = 3,
-    Multiplicative = 4,
-    Prefix = 5,
-    FnCall = 6,
-    Index = 7,
+    Assignment = 1,
+    Coalesce = 2,
+    LogicalOr = 3,
+    LogicalAnd = 4,
+    Equality = 5,
+    Comparative = 6,
+    Range = 7,
+    BitOr = 8,
+    BitXor = 9,
+    BitAnd = 10,
+    Shift = 11,
+    Additive = 12,
+    Multiplicative = 13,
+    Prefix = 14,
+    /// Binds tighter than prefix operators so `-2 ** 2` is `-(2 ** 2)`
+    Exponent = 15,
+    FnCall = 16,
+    Index = 17,
+    Member = 18,
 }
 
 impl From<&Token> for Precedence {
     fn from(value: &Token) -> Self {
         match value.kind {
+            TokenKind::Assign
+            | TokenKind::PlusAssign
+            | TokenKind::MinusAssign
+            | TokenKind::AsteriskAssign
+            | TokenKind::ForwardSlashAssign
+            | TokenKind::ModuloAssign => Precedence::Assignment,
+            TokenKind::NullCoalesce => Precedence::Coalesce,
+            TokenKind::Or => Precedence::LogicalOr,
+            TokenKind::And => Precedence::LogicalAnd,
             TokenKind::Equal | TokenKind::NotEqual => Precedence::Equality,
             TokenKind::LessThan
             | TokenKind::LessThanEqual
             | TokenKind::GreaterThan
-            | TokenKind::GreaterThanEqual => Precedence::Index,
-            TokenKind::Plus | TokenKind::Minus | TokenKind::Or => Precedence::Additive,
-            TokenKind::ForwardSlash | TokenKind::Asterisk | TokenKind::Modulo | TokenKind::And => {
-                Precedence::Multiplicative
-            }
+            | TokenKind::GreaterThanEqual => Precedence::Comparative,
+            TokenKind::DotDot => Precedence::Range,
+            TokenKind::BitOr => Precedence::BitOr,
+            TokenKind::BitXor => Precedence::BitXor,
+            TokenKind::BitAnd => Precedence::BitAnd,
+            TokenKind::ShiftLeft | TokenKind::ShiftRight => Precedence::Shift,
+            TokenKind::Plus | TokenKind::Minus => Precedence::Additive,
+            TokenKind::ForwardSlash
+            | TokenKind::Asterisk
+            | TokenKind::Modulo
+            | TokenKind::IntDivide => Precedence::Multiplicative,
+            TokenKind::Power => Precedence::Exponent,
             TokenKind::LParen => Precedence::FnCall,
-            TokenKind::LBracket => Precedence::Index,
+            TokenKind::LBracket | TokenKind::OptionalIndex => Precedence::Index,
+            TokenKind::Dot | TokenKind::OptionalDot => Precedence::Member,
             _ => Precedence::Lowest,
         }
     }
 }
+
+impl Precedence {
+    /// The next looser level
+    pub fn below(&self) -> Precedence {
+        match self {
+            Precedence::Lowest | Precedence::Assignment => Precedence::Lowest,
+            Precedence::Coalesce => Precedence::Assignment,
+            Precedence::LogicalOr => Precedence::Coalesce,
+            Precedence::LogicalAnd => Precedence::LogicalOr,
+            Precedence::Equality => Precedence::LogicalAnd,
+            Precedence::Comparative => Precedence::Equality,
+            Precedence::Range => Precedence::Comparative,
+            Precedence::BitOr => Precedence::Range,
+            Precedence::BitXor => Precedence::BitOr,
+            Precedence::BitAnd => Precedence::BitXor,
+            Precedence::Shift => Precedence::BitAnd,
+            Precedence::Additive => Precedence::Shift,
+            Precedence::Multiplicative => Precedence::Additive,
+            Precedence::Prefix => Precedence::Multiplicative,
+            Precedence::Exponent => Precedence::Prefix,
+            Precedence::FnCall => Precedence::Exponent,
+            Precedence::Index => Precedence::FnCall,
+            Precedence::Member => Precedence::Index,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a = b = c` is `a = (b = c)` and `a ** b ** c` is `a ** (b ** c)`
+    Right,
+}
+
+impl From<&TokenKind> for Associativity {
+    fn from(value: &TokenKind) -> Self {
+        match value {
+            TokenKind::Assign
+            | TokenKind::PlusAssign
+            | TokenKind::MinusAssign
+            | TokenKind::AsteriskAssign
+            | TokenKind::ForwardSlashAssign
+            | TokenKind::ModuloAssign
+            | TokenKind::Power
+            | TokenKind::NullCoalesce => Associativity::Right,
+            _ => Associativity::Left,
+        }
+    }
+}
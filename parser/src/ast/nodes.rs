@@ -1,4 +1,3 @@
-use core::panic;
 use std::fmt::Display;
 
 use lace_lexer::token::{
@@ -9,10 +8,16 @@ use lace_lexer::token::{
 };
 
 use crate::{
-    ast::{statement::BlockStatement, Expression, Precedence},
+    ast::{
+        pattern::Pattern,
+        statement::{BlockStatement, Statement},
+        Associativity, Expression, Precedence,
+    },
     errors::{
-        CondIssue, ExpectedIdent, ExpectedNumber, FuncError, FuncIssue, IncompleteConditional,
-        NoPrefixParser, NumKind, UnterminatedKind, UnterminatedLiteral,
+        misspelled, CondIssue, ExpectedIdent, ExpectedNumber, FuncError, FuncIssue,
+        IncompleteConditional, IncompleteLoop, IncompleteMatch, InvalidAssignment, InvalidEscape,
+        LoopIssue, MatchIssue, MisspelledKeyword, NoPrefixParser, NumKind, UnterminatedKind,
+        UnterminatedLiteral,
     },
     Parser, ParserResult,
 };
@@ -30,16 +35,8 @@ impl Display for IdentNode {
 }
 
 impl IdentNode {
-    pub fn new(token: Token) -> Self {
-        if let TokenKind::Ident { label } = &token.kind {
-            Self {
-                token: token.clone(),
-                label: label.into(),
-            }
-        } else {
-            println!("Received {}", token);
-            panic!("This function shouldn't have been called");
-        }
+    pub fn span(&self) -> Span {
+        self.token.span.clone()
     }
 
     pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
@@ -54,6 +51,30 @@ impl IdentNode {
     }
 }
 
+/// A primitive value along with where it was written
+#[derive(Debug, Clone)]
+pub struct LiteralNode {
+    pub val: PrimitiveNode,
+    pub span: Span,
+}
+
+eq_without_span!(LiteralNode, val);
+
+impl Display for LiteralNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+impl LiteralNode {
+    pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        Ok(LiteralNode {
+            val: PrimitiveNode::parse(parser)?,
+            span: parser.curr_token.span.clone(),
+        })
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum PrimitiveNode {
     IntegerLiteral(i64),
@@ -61,6 +82,7 @@ pub enum PrimitiveNode {
     CharLiteral(char),
     StringLiteral(String),
     BooleanLiteral(bool),
+    NullLiteral,
 }
 
 impl Display for PrimitiveNode {
@@ -71,6 +93,7 @@ impl Display for PrimitiveNode {
             PrimitiveNode::CharLiteral(val) => write!(f, "'{}' (Char)", val),
             PrimitiveNode::StringLiteral(val) => write!(f, "\"{}\" (Str)", val),
             PrimitiveNode::BooleanLiteral(val) => write!(f, "{} (Bool)", val),
+            PrimitiveNode::NullLiteral => write!(f, "null"),
         }
     }
 }
@@ -80,7 +103,7 @@ impl PrimitiveNode {
         match &parser.curr_token.kind {
             TokenKind::Literal { kind, val } => {
                 match kind {
-                    LiteralKind::Int => match val.parse::<i64>() {
+                    LiteralKind::Int { radix } => match i64::from_str_radix(val, *radix as u32) {
                         Ok(val) => Ok(PrimitiveNode::IntegerLiteral(val)),
                         Err(_) => Err(Box::new(ExpectedNumber::new(
                             NumKind::Int,
@@ -97,16 +120,26 @@ impl PrimitiveNode {
                     LiteralKind::Char { terminated } => match terminated {
                         // TODO: Maybe find a better way to do this
                         true => Ok(PrimitiveNode::CharLiteral(val.chars().nth(0).unwrap())),
-                        false => Err(Box::new(UnterminatedLiteral::from(UnterminatedKind::Char))),
+                        false => Err(Box::new(UnterminatedLiteral::new(
+                            UnterminatedKind::Char,
+                            parser.curr_token.span.clone(),
+                        ))),
                     },
                     LiteralKind::Str { terminated } => match terminated {
                         true => Ok(PrimitiveNode::StringLiteral(val.into())),
-                        false => Err(Box::new(UnterminatedLiteral::from(UnterminatedKind::Str))),
+                        false => Err(Box::new(UnterminatedLiteral::new(
+                            UnterminatedKind::Str,
+                            parser.curr_token.span.clone(),
+                        ))),
                     },
                 }
             }
+            TokenKind::BadEscape { seq, span } => {
+                Err(Box::new(InvalidEscape::new(seq.into(), span.clone())))
+            }
             TokenKind::True => Ok(PrimitiveNode::BooleanLiteral(true)),
             TokenKind::False => Ok(PrimitiveNode::BooleanLiteral(false)),
+            TokenKind::Null => Ok(PrimitiveNode::NullLiteral),
             _ => Err(Box::new(NoPrefixParser {
                 token: parser.curr_token.clone(),
             })),
@@ -114,12 +147,15 @@ impl PrimitiveNode {
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct PrefixOperator {
     pub operator: Token,
     pub right_expr: Box<Expression>,
+    pub span: Span,
 }
 
+eq_without_span!(PrefixOperator, operator, right_expr);
+
 impl Display for PrefixOperator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Prefix({}){}", self.operator, self.right_expr)
@@ -129,6 +165,7 @@ impl Display for PrefixOperator {
 impl PrefixOperator {
     pub fn new(token: Token, right: Expression) -> Self {
         Self {
+            span: token.span.to(&right.span()),
             operator: token,
             right_expr: Box::new(right),
         }
@@ -143,13 +180,16 @@ impl PrefixOperator {
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct InfixOperator {
     pub operator: Token,
     pub left_expr: Box<Expression>,
     pub right_expr: Box<Expression>,
+    pub span: Span,
 }
 
+eq_without_span!(InfixOperator, operator, left_expr, right_expr);
+
 impl Display for InfixOperator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -163,6 +203,7 @@ impl Display for InfixOperator {
 impl InfixOperator {
     pub fn new(token: Token, left_expr: Expression, right_expr: Expression) -> Self {
         Self {
+            span: left_expr.span().to(&right_expr.span()),
             operator: token,
             left_expr: Box::new(left_expr),
             right_expr: Box::new(right_expr),
@@ -171,7 +212,13 @@ impl InfixOperator {
 
     pub fn parse(parser: &mut Parser, left_expr: Expression) -> ParserResult<Self> {
         let token = parser.curr_token.clone();
-        let precedence = parser.curr_precedence();
+        let mut precedence = parser.curr_precedence();
+
+        // operators of the same precedence on the right
+        // are only allowed to bind first for right associative ones
+        if Associativity::from(&token.kind) == Associativity::Right {
+            precedence = precedence.below();
+        }
 
         parser.next_token();
 
@@ -181,13 +228,75 @@ impl InfixOperator {
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+/// Updates an existing binding or an element of a collection.
+/// The operator is either a plain '=' or a compound assignment like '+='
+#[derive(Debug, Clone)]
+pub struct Assignment {
+    pub target: Box<Expression>,
+    pub operator: Token,
+    pub val: Box<Expression>,
+    pub span: Span,
+}
+
+eq_without_span!(Assignment, target, operator, val);
+
+impl Display for Assignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Assign => {{ {} | {} | {} }}",
+            self.target, self.operator, self.val
+        )
+    }
+}
+
+impl Assignment {
+    pub fn parse(parser: &mut Parser, target: Expression) -> ParserResult<Self> {
+        let operator = parser.curr_token.clone();
+
+        if !Self::assignable(&target) {
+            return Err(Box::new(InvalidAssignment {
+                operator,
+                in_condition: parser.in_condition(),
+            }));
+        }
+
+        parser.next_token();
+
+        // assignments are right associative so the value
+        // is allowed to be another assignment
+        let val = Expression::parse(parser, Precedence::Lowest)?;
+
+        Ok(Assignment {
+            span: target.span().to(&val.span()),
+            target: Box::new(target),
+            operator,
+            val: Box::new(val),
+        })
+    }
+
+    /// Variables and indexes into them, like `grid[0][1]`
+    fn assignable(target: &Expression) -> bool {
+        match target {
+            Expression::Identifier(_) => true,
+            Expression::ArrIndex(index_access) => {
+                !index_access.optional && Self::assignable(&index_access.arr)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct ConditionalOperator {
     pub cond: Box<Expression>,
     pub consequence: BlockStatement,
     pub alternative: Option<BlockStatement>,
+    pub span: Span,
 }
 
+eq_without_span!(ConditionalOperator, cond, consequence, alternative);
+
 impl Display for ConditionalOperator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut expr = format!(
@@ -207,48 +316,75 @@ impl Display for ConditionalOperator {
 
 impl ConditionalOperator {
     pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
         let start_pos = parser.lexer.curr_pos();
-        if !parser.expect_peek(&dummy_token(TokenKind::LParen)) {
+        if !parser.accept_peek(&dummy_token(TokenKind::LParen)) {
             return Err(Box::new(IncompleteConditional::new(
                 start_pos,
                 CondIssue::ExprIncorrectlyOpened,
                 None,
-                parser.lexer.curr_col(),
             )));
         }
 
         parser.next_token();
-        let cond = Expression::parse(parser, Precedence::Lowest)?;
+        let cond = parser.parse_condition()?;
 
-        if !parser.expect_peek(&dummy_token(TokenKind::RParen)) {
+        if !parser.accept_peek(&dummy_token(TokenKind::RParen)) {
             return Err(Box::new(IncompleteConditional::new(
                 start_pos,
                 CondIssue::ExprIncorrectlyClosed,
                 Some(parser.lexer.curr_pos()),
-                parser.lexer.curr_col() - 1,
             )));
         }
 
-        if !parser.expect_peek(&dummy_token(TokenKind::LCurly)) {
+        if !parser.accept_peek(&dummy_token(TokenKind::LCurly)) {
             return Err(Box::new(IncompleteConditional::new(
                 start_pos,
                 CondIssue::BodyIncorrectlyOpened,
                 Some(parser.lexer.curr_pos()),
-                parser.lexer.curr_col(),
             )));
         }
 
         let consequence = BlockStatement::parse(parser);
         let mut alternative = None;
 
+        // only a word on the same line as the closing brace
+        // is taken for a misspelled else
+        if let Some(keyword) = misspelled(&parser.peeked_token, &TokenKind::Else) {
+            if parser.peeked_token.span.start_line == parser.curr_token.span.end_line {
+                return Err(Box::new(MisspelledKeyword::new(
+                    parser.peeked_token.clone(),
+                    keyword,
+                )));
+            }
+        }
+
         if parser.peek_token_is(&TokenKind::Else) {
             parser.next_token();
-            if !parser.expect_peek(&dummy_token(TokenKind::LCurly)) {
+
+            // an else if is stored as an alternative holding only
+            // the next conditional so that chains nest naturally
+            if parser.peek_token_is(&TokenKind::If) {
+                parser.next_token();
+                let chained = ConditionalOperator::parse(parser)?;
+                let span = chained.span.clone();
+
+                return Ok(ConditionalOperator {
+                    cond: Box::new(cond),
+                    consequence,
+                    alternative: Some(BlockStatement {
+                        statements: vec![Statement::Expression(Expression::Conditional(chained))],
+                        span: span.clone(),
+                    }),
+                    span: start.to(&span),
+                });
+            }
+
+            if !parser.accept_peek(&dummy_token(TokenKind::LCurly)) {
                 return Err(Box::new(IncompleteConditional::new(
                     start_pos,
                     CondIssue::ExpectedElse,
                     Some(parser.lexer.curr_pos()),
-                    parser.lexer.curr_col(),
                 )));
             }
 
@@ -259,17 +395,313 @@ impl ConditionalOperator {
             cond: Box::new(cond),
             consequence,
             alternative,
+            span: start.to(&parser.curr_token.span),
         })
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
+pub struct WhileLoop {
+    pub cond: Box<Expression>,
+    pub body: BlockStatement,
+    pub span: Span,
+}
+
+eq_without_span!(WhileLoop, cond, body);
+
+impl Display for WhileLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "While => {{ condition => {} | body => {{\n{}}} \n}}",
+            self.cond, self.body
+        )
+    }
+}
+
+impl WhileLoop {
+    pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
+        if !parser.accept_peek(&dummy_token(TokenKind::LParen)) {
+            return Err(Box::new(IncompleteLoop::new(
+                start,
+                LoopIssue::ExprIncorrectlyOpened,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        parser.next_token();
+        let cond = parser.parse_condition()?;
+
+        if !parser.accept_peek(&dummy_token(TokenKind::RParen)) {
+            return Err(Box::new(IncompleteLoop::new(
+                start,
+                LoopIssue::ExprIncorrectlyClosed,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        if !parser.accept_peek(&dummy_token(TokenKind::LCurly)) {
+            return Err(Box::new(IncompleteLoop::new(
+                start,
+                LoopIssue::BodyIncorrectlyOpened,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        let body = BlockStatement::parse(parser);
+
+        Ok(WhileLoop {
+            cond: Box::new(cond),
+            body,
+            span: start.to(&parser.curr_token.span),
+        })
+    }
+}
+
+/// Iterates over a collection.
+/// A single binding receives the element (or the key for hashmaps)
+/// while two bindings receive the index or key followed by the element
+#[derive(Debug, Clone)]
+pub struct ForLoop {
+    pub bindings: Vec<IdentNode>,
+    pub iterable: Box<Expression>,
+    pub body: BlockStatement,
+    pub span: Span,
+}
+
+eq_without_span!(ForLoop, bindings, iterable, body);
+
+impl Display for ForLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bindings: Vec<String> = self.bindings.iter().map(ToString::to_string).collect();
+
+        write!(
+            f,
+            "For => {{ bindings => ({}) | iterable => {} | body => {{\n{}}} \n}}",
+            bindings.join(", "),
+            self.iterable,
+            self.body
+        )
+    }
+}
+
+impl ForLoop {
+    pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
+        if !parser.accept_peek(&dummy_token(TokenKind::LParen)) {
+            return Err(Box::new(IncompleteLoop::new(
+                start,
+                LoopIssue::ExprIncorrectlyOpened,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        parser.next_token();
+        let mut bindings = vec![IdentNode::parse(parser)?];
+
+        if parser.peek_token_is(&TokenKind::Comma) {
+            parser.next_token(); // skip the comma
+            parser.next_token(); // capture the second binding
+            bindings.push(IdentNode::parse(parser)?);
+        }
+
+        if !parser.accept_peek(&dummy_token(TokenKind::In)) {
+            return Err(Box::new(IncompleteLoop::new(
+                start,
+                LoopIssue::ExpectedIn,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        parser.next_token();
+        let iterable = Expression::parse(parser, Precedence::Lowest)?;
+
+        if !parser.accept_peek(&dummy_token(TokenKind::RParen)) {
+            return Err(Box::new(IncompleteLoop::new(
+                start,
+                LoopIssue::ExprIncorrectlyClosed,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        if !parser.accept_peek(&dummy_token(TokenKind::LCurly)) {
+            return Err(Box::new(IncompleteLoop::new(
+                start,
+                LoopIssue::BodyIncorrectlyOpened,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        let body = BlockStatement::parse(parser);
+
+        Ok(ForLoop {
+            bindings,
+            iterable: Box::new(iterable),
+            body,
+            span: start.to(&parser.curr_token.span),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expression>,
+    pub body: BlockStatement,
+    pub span: Span,
+}
+
+eq_without_span!(MatchArm, pattern, guard, body);
+
+impl Display for MatchArm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.guard {
+            Some(guard) => write!(f, "{} if {} => {{\n{}}}", self.pattern, guard, self.body),
+            None => write!(f, "{} => {{\n{}}}", self.pattern, self.body),
+        }
+    }
+}
+
+/// Evaluates the body of the first arm whose pattern
+/// matches the value and whose guard holds
+#[derive(Debug, Clone)]
+pub struct MatchExpression {
+    pub scrutinee: Box<Expression>,
+    pub arms: Vec<MatchArm>,
+    pub span: Span,
+}
+
+eq_without_span!(MatchExpression, scrutinee, arms);
+
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arms: Vec<String> = self.arms.iter().map(ToString::to_string).collect();
+
+        write!(
+            f,
+            "Match => {{ value => {} | arms => {{\n{}\n}} \n}}",
+            self.scrutinee,
+            arms.join(",\n")
+        )
+    }
+}
+
+impl MatchExpression {
+    pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
+        if !parser.accept_peek(&dummy_token(TokenKind::LParen)) {
+            return Err(Box::new(IncompleteMatch::new(
+                start,
+                MatchIssue::ExprIncorrectlyOpened,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        parser.next_token();
+        let scrutinee = Expression::parse(parser, Precedence::Lowest)?;
+
+        if !parser.accept_peek(&dummy_token(TokenKind::RParen)) {
+            return Err(Box::new(IncompleteMatch::new(
+                start,
+                MatchIssue::ExprIncorrectlyClosed,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        if !parser.accept_peek(&dummy_token(TokenKind::LCurly)) {
+            return Err(Box::new(IncompleteMatch::new(
+                start,
+                MatchIssue::BodyIncorrectlyOpened,
+                Some(parser.peeked_token.span.clone()),
+            )));
+        }
+
+        parser.next_token();
+        let mut arms = Vec::new();
+
+        while !parser.curr_token_is(&dummy_token(TokenKind::RCurly)) {
+            if parser.curr_token.reached_eof() {
+                return Err(Box::new(IncompleteMatch::new(
+                    start,
+                    MatchIssue::BodyIncorrectlyClosed,
+                    Some(parser.curr_token.span.clone()),
+                )));
+            }
+
+            arms.push(Self::parse_arm(parser)?);
+
+            if parser.peek_token_is(&TokenKind::Comma) {
+                parser.next_token();
+            }
+            parser.next_token();
+        }
+
+        if arms.is_empty() {
+            return Err(Box::new(IncompleteMatch::new(
+                start,
+                MatchIssue::NoArms,
+                Some(parser.curr_token.span.clone()),
+            )));
+        }
+
+        Ok(MatchExpression {
+            scrutinee: Box::new(scrutinee),
+            arms,
+            span: start.to(&parser.curr_token.span),
+        })
+    }
+
+    fn parse_arm(parser: &mut Parser) -> ParserResult<MatchArm> {
+        let pattern = Pattern::parse(parser)?;
+
+        let mut guard = None;
+        if parser.peek_token_is(&TokenKind::If) {
+            parser.next_token();
+            parser.next_token();
+            guard = Some(Expression::parse(parser, Precedence::Lowest)?);
+        }
+
+        if !parser.accept_peek(&dummy_token(TokenKind::FatArrow)) {
+            return Err(Box::new(IncompleteMatch::new(
+                parser.peeked_token.span.clone(),
+                MatchIssue::ExpectedArrow,
+                None,
+            )));
+        }
+
+        parser.next_token();
+
+        // braces after the arrow open a block rather than a hashmap
+        let body = if parser.curr_token_is(&dummy_token(TokenKind::LCurly)) {
+            BlockStatement::parse(parser)
+        } else {
+            let expr = Expression::parse(parser, Precedence::Lowest)?;
+            BlockStatement {
+                span: expr.span(),
+                statements: vec![Statement::Expression(expr)],
+            }
+        };
+
+        Ok(MatchArm {
+            span: pattern.span().to(&body.span),
+            pattern,
+            guard,
+            body,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct FunctionLiteral {
     pub name: Option<String>,
     pub params: Vec<IdentNode>,
     pub body: BlockStatement,
+    pub span: Span,
 }
 
+eq_without_span!(FunctionLiteral, name, params, body);
+
 impl Display for FunctionLiteral {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let params: Vec<String> = self.params.iter().map(ToString::to_string).collect();
@@ -294,33 +726,36 @@ impl Display for FunctionLiteral {
 
 impl FunctionLiteral {
     pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
         let start_pos = parser.lexer.curr_pos();
         let name = Self::parse_function_name(parser);
 
-        if !parser.expect_peek(&dummy_token(TokenKind::LParen)) {
+        if !parser.accept_peek(&dummy_token(TokenKind::LParen)) {
             return Err(Box::new(FuncError::new(
                 start_pos,
                 FuncIssue::FuncMissingParens,
                 None,
-                parser.lexer.curr_col(),
             )));
         }
 
         let params = Self::parse_function_params(parser, &start_pos)?;
 
-        if !parser.expect_peek(&dummy_token(TokenKind::LCurly)) {
-            println!("Whoops {}", parser.lexer.curr_col());
+        if !parser.accept_peek(&dummy_token(TokenKind::LCurly)) {
             return Err(Box::new(FuncError::new(
                 start_pos,
                 FuncIssue::BodyIncorrectlyOpened,
                 Some(parser.lexer.curr_pos()),
-                parser.lexer.curr_col(),
             )));
         }
 
         let body = BlockStatement::parse(parser);
 
-        Ok(FunctionLiteral { name, params, body })
+        Ok(FunctionLiteral {
+            name,
+            params,
+            span: start.to(&body.span),
+            body,
+        })
     }
 
     fn parse_function_name(parser: &mut Parser) -> Option<String> {
@@ -344,23 +779,20 @@ impl FunctionLiteral {
 
         parser.next_token();
 
-        let mut ident = IdentNode::new(parser.curr_token.clone());
-        idents.push(ident);
+        idents.push(IdentNode::parse(parser)?);
 
         while parser.peek_token_is(&TokenKind::Comma) {
             parser.next_token(); // skip the comma
             parser.next_token(); // capture the param
 
-            ident = IdentNode::new(parser.curr_token.clone());
-            idents.push(ident);
+            idents.push(IdentNode::parse(parser)?);
         }
 
-        if !parser.expect_peek(&dummy_token(TokenKind::RParen)) {
+        if !parser.accept_peek(&dummy_token(TokenKind::RParen)) {
             return Err(Box::new(FuncError::new(
                 start.clone(),
                 FuncIssue::DefIncorrectlyClosed,
                 Some(parser.lexer.curr_pos()),
-                parser.lexer.curr_col(),
             )));
         }
 
@@ -368,12 +800,15 @@ impl FunctionLiteral {
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct FunctionCall {
     pub function: Box<Expression>,
     pub args: Vec<Expression>,
+    pub span: Span,
 }
 
+eq_without_span!(FunctionCall, function, args);
+
 impl Display for FunctionCall {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let args: Vec<String> = self.args.iter().map(ToString::to_string).collect();
@@ -392,17 +827,21 @@ impl FunctionCall {
         let args = Expression::parse_expr_list(parser, &dummy_token(TokenKind::RParen))?;
 
         Ok(FunctionCall {
+            span: function.span().to(&parser.curr_token.span),
             function: Box::new(function),
             args,
         })
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct ArrayLiteral {
     pub elements: Vec<Expression>,
+    pub span: Span,
 }
 
+eq_without_span!(ArrayLiteral, elements);
+
 impl Display for ArrayLiteral {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let elements = self
@@ -417,46 +856,106 @@ impl Display for ArrayLiteral {
 
 impl ArrayLiteral {
     pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
         let elements = Expression::parse_expr_list(parser, &dummy_token(TokenKind::RBracket))?;
-        Ok(Self { elements })
+        Ok(Self {
+            elements,
+            span: start.to(&parser.curr_token.span),
+        })
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct IndexAccess {
     pub arr: Box<Expression>,
     pub index: Box<Expression>,
+    /// `?[` gives null instead of failing when the collection
+    /// is null or doesn't hold the index. A null collection
+    /// also skips the rest of the chain
+    pub optional: bool,
+    pub span: Span,
 }
 
+eq_without_span!(IndexAccess, arr, index, optional);
+
 impl Display for IndexAccess {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Array => {} | Index => {}", self.arr, self.index)
+        let optional = if self.optional { "?" } else { "" };
+        write!(
+            f,
+            "Array => {} | Index{} => {}",
+            self.arr, optional, self.index
+        )
     }
 }
 impl IndexAccess {
     pub fn parse(parser: &mut Parser, left_expr: Expression) -> ParserResult<Self> {
+        let optional = parser.curr_token.kind == TokenKind::OptionalIndex;
         parser.next_token();
         let index = Expression::parse(parser, Precedence::Lowest)?;
-        if !parser.expect_peek(&dummy_token(TokenKind::RBracket)) {
-            // TODO: this does not belong here. Change it
-            // return Err(Box::new(FuncError::new(
-            //     None,
-            //     FuncIssue::BodyIncorrectlyClosed,
-            // )));
+        let closing = dummy_token(TokenKind::RBracket);
+        if !parser.accept_peek(&closing) {
+            return Err(parser.unexpected_peek(&closing));
         }
 
         Ok(IndexAccess {
+            span: left_expr.span().to(&parser.curr_token.span),
             arr: Box::new(left_expr),
             index: Box::new(index),
+            optional,
         })
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+/// Accesses a field of a hashmap or, when called,
+/// a method that receives the object as its first argument
+#[derive(Debug, Clone)]
+pub struct MemberAccess {
+    pub object: Box<Expression>,
+    pub member: IdentNode,
+    /// `?.` gives null instead of failing when the object
+    /// is null or doesn't have the field. A null object
+    /// also skips the rest of the chain
+    pub optional: bool,
+    pub span: Span,
+}
+
+eq_without_span!(MemberAccess, object, member, optional);
+
+impl Display for MemberAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let optional = if self.optional { "?" } else { "" };
+        write!(
+            f,
+            "Object => {} | Member{} => {}",
+            self.object, optional, self.member
+        )
+    }
+}
+
+impl MemberAccess {
+    pub fn parse(parser: &mut Parser, object: Expression) -> ParserResult<Self> {
+        let optional = parser.curr_token.kind == TokenKind::OptionalDot;
+        parser.next_token();
+        let member = IdentNode::parse(parser)?;
+
+        Ok(MemberAccess {
+            span: object.span().to(&member.span()),
+            object: Box::new(object),
+            member,
+            optional,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct HashLiteral {
     pub pairs: Vec<(Expression, Expression)>,
+    pub span: Span,
 }
 
+eq_without_span!(HashLiteral, pairs);
+
 impl Display for HashLiteral {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let pairs = self
@@ -471,17 +970,15 @@ impl Display for HashLiteral {
 
 impl HashLiteral {
     pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
         let mut pairs = Vec::new();
 
         while !parser.peek_token_is(&TokenKind::RCurly) {
             parser.next_token();
             let key = Expression::parse(parser, Precedence::Lowest)?;
-            if !parser.expect_peek(&dummy_token(TokenKind::Colon)) {
-                // TODO: this does not belong here. Change it
-                // return Err(Box::new(FuncError::new(
-                //     None,
-                //     FuncIssue::BodyIncorrectlyClosed,
-                // )));
+            let colon = dummy_token(TokenKind::Colon);
+            if !parser.accept_peek(&colon) {
+                return Err(parser.unexpected_peek(&colon));
             }
 
             parser.next_token();
@@ -489,24 +986,17 @@ impl HashLiteral {
 
             pairs.push((key, val));
 
-            if !parser.peek_token_is(&TokenKind::RCurly)
-                && !parser.expect_peek(&dummy_token(TokenKind::Comma))
-            {
-                // TODO: this does not belong here. Change it
-                // return Err(Box::new(FuncError::new(
-                //     None,
-                //     FuncIssue::BodyIncorrectlyClosed,
-                // )));
+            let comma = dummy_token(TokenKind::Comma);
+            if !parser.peek_token_is(&TokenKind::RCurly) && !parser.accept_peek(&comma) {
+                return Err(parser.unexpected_peek(&comma));
             }
         }
 
-        if !parser.expect_peek(&dummy_token(TokenKind::RCurly)) {
-            // return Err(Box::new(FuncError::new(
-            //     None,
-            //     FuncIssue::BodyIncorrectlyClosed,
-            // )));
-        }
+        parser.next_token();
 
-        Ok(HashLiteral { pairs })
+        Ok(HashLiteral {
+            pairs,
+            span: start.to(&parser.curr_token.span),
+        })
     }
 }
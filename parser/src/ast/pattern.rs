@@ -0,0 +1,219 @@
+use std::fmt::Display;
+
+use lace_lexer::token::{dummy_token, kind::TokenKind, span::Span, Token};
+
+use crate::{
+    ast::nodes::{IdentNode, LiteralNode, PrimitiveNode},
+    errors::{IncompleteMatch, MatchIssue},
+    Parser, ParserResult,
+};
+
+/// Shapes a value is checked against in a match arm
+#[derive(PartialEq, Debug, Clone)]
+pub enum Pattern {
+    /// _
+    Wildcard(Token),
+    Literal(LiteralNode),
+    /// Matches anything and binds it to the name
+    Binding(IdentNode),
+    Array(ArrayPattern),
+    HashMap(HashPattern),
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Wildcard(_) => write!(f, "_"),
+            Pattern::Literal(x) => write!(f, "{x}"),
+            Pattern::Binding(x) => write!(f, "{x}"),
+            Pattern::Array(x) => write!(f, "{x}"),
+            Pattern::HashMap(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+impl Pattern {
+    pub fn span(&self) -> Span {
+        match self {
+            Pattern::Wildcard(token) => token.span.clone(),
+            Pattern::Literal(x) => x.span.clone(),
+            Pattern::Binding(x) => x.span(),
+            Pattern::Array(x) => x.span.clone(),
+            Pattern::HashMap(x) => x.span.clone(),
+        }
+    }
+
+    pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        match &parser.curr_token.kind {
+            TokenKind::Ident { label } if label == "_" => {
+                Ok(Pattern::Wildcard(parser.curr_token.clone()))
+            }
+            TokenKind::Ident { label: _ } => IdentNode::parse(parser).map(Pattern::Binding),
+            TokenKind::Literal { kind: _, val: _ }
+            | TokenKind::BadEscape { seq: _, span: _ }
+            | TokenKind::True
+            | TokenKind::False
+            | TokenKind::Null => LiteralNode::parse(parser).map(Pattern::Literal),
+            TokenKind::Minus => Self::parse_negative(parser),
+            TokenKind::LBracket => ArrayPattern::parse(parser).map(Pattern::Array),
+            TokenKind::LCurly => HashPattern::parse(parser).map(Pattern::HashMap),
+            _ => Err(Self::invalid(parser)),
+        }
+    }
+
+    fn parse_negative(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
+        parser.next_token();
+
+        let val = match PrimitiveNode::parse(parser)? {
+            PrimitiveNode::IntegerLiteral(i) => PrimitiveNode::IntegerLiteral(-i),
+            PrimitiveNode::FloatLiteral(f) => PrimitiveNode::FloatLiteral(-f),
+            _ => return Err(Self::invalid(parser)),
+        };
+
+        Ok(Pattern::Literal(LiteralNode {
+            val,
+            span: start.to(&parser.curr_token.span),
+        }))
+    }
+
+    fn invalid(parser: &Parser) -> Box<IncompleteMatch> {
+        Box::new(IncompleteMatch::new(
+            parser.curr_token.span.clone(),
+            MatchIssue::InvalidPattern,
+            None,
+        ))
+    }
+}
+
+/// Matches arrays element by element.
+/// A rest pattern like `..tail` matches any number of elements
+/// and optionally binds them
+#[derive(Debug, Clone)]
+pub struct ArrayPattern {
+    pub before: Vec<Pattern>,
+    pub rest: Option<Option<IdentNode>>,
+    pub after: Vec<Pattern>,
+    pub span: Span,
+}
+
+eq_without_span!(ArrayPattern, before, rest, after);
+
+impl Display for ArrayPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut elements: Vec<String> = self.before.iter().map(ToString::to_string).collect();
+
+        if let Some(rest) = &self.rest {
+            match rest {
+                Some(name) => elements.push(format!("..{}", name)),
+                None => elements.push("..".into()),
+            }
+            elements.extend(self.after.iter().map(ToString::to_string));
+        }
+
+        write!(f, "[{}]", elements.join(", "))
+    }
+}
+
+impl ArrayPattern {
+    pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let mut pattern = ArrayPattern {
+            before: Vec::new(),
+            rest: None,
+            after: Vec::new(),
+            span: parser.curr_token.span.clone(),
+        };
+
+        while !parser.peek_token_is(&TokenKind::RBracket) {
+            parser.next_token();
+
+            if parser.curr_token_is(&dummy_token(TokenKind::DotDot)) && pattern.rest.is_none() {
+                let mut name = None;
+                if let TokenKind::Ident { label: _ } = parser.peeked_token.kind {
+                    parser.next_token();
+                    name = Some(IdentNode::parse(parser)?);
+                }
+                pattern.rest = Some(name);
+            } else if pattern.rest.is_some() {
+                pattern.after.push(Pattern::parse(parser)?);
+            } else {
+                pattern.before.push(Pattern::parse(parser)?);
+            }
+
+            if !parser.peek_token_is(&TokenKind::RBracket)
+                && !parser.accept_peek(&dummy_token(TokenKind::Comma))
+            {
+                return Err(Self::unclosed(parser));
+            }
+        }
+
+        parser.next_token();
+        pattern.span = pattern.span.to(&parser.curr_token.span);
+
+        Ok(pattern)
+    }
+
+    fn unclosed(parser: &Parser) -> Box<IncompleteMatch> {
+        Box::new(IncompleteMatch::new(
+            parser.peeked_token.span.clone(),
+            MatchIssue::InvalidPattern,
+            None,
+        ))
+    }
+}
+
+/// Matches hashmaps that contain every listed key
+/// with a value matching the corresponding pattern
+#[derive(Debug, Clone)]
+pub struct HashPattern {
+    pub pairs: Vec<(LiteralNode, Pattern)>,
+    pub span: Span,
+}
+
+eq_without_span!(HashPattern, pairs);
+
+impl Display for HashPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs = self
+            .pairs
+            .iter()
+            .map(|(key, val)| format!("{}: {}", key, val))
+            .collect::<Vec<String>>();
+
+        write!(f, "{{ {} }}", pairs.join(", "))
+    }
+}
+
+impl HashPattern {
+    pub fn parse(parser: &mut Parser) -> ParserResult<Self> {
+        let start = parser.curr_token.span.clone();
+        let mut pairs = Vec::new();
+
+        while !parser.peek_token_is(&TokenKind::RCurly) {
+            parser.next_token();
+            let key = LiteralNode::parse(parser)?;
+
+            if !parser.accept_peek(&dummy_token(TokenKind::Colon)) {
+                return Err(ArrayPattern::unclosed(parser));
+            }
+
+            parser.next_token();
+            let val = Pattern::parse(parser)?;
+
+            pairs.push((key, val));
+
+            if !parser.peek_token_is(&TokenKind::RCurly)
+                && !parser.accept_peek(&dummy_token(TokenKind::Comma))
+            {
+                return Err(ArrayPattern::unclosed(parser));
+            }
+        }
+
+        parser.next_token();
+
+        Ok(HashPattern {
+            pairs,
+            span: start.to(&parser.curr_token.span),
+        })
+    }
+}
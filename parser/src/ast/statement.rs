@@ -1,6 +1,6 @@
 use std::{fmt::Display, path::PathBuf};
 
-use lace_lexer::token::{dummy_token, kind::TokenKind};
+use lace_lexer::token::{dummy_token, kind::TokenKind, span::Span, Token};
 
 use crate::{
     ast::{nodes::IdentNode, Expression},
@@ -13,6 +13,10 @@ pub enum Statement {
     Return(ReturnStatement),
     Expression(Expression),
     Source(SourceStatement),
+    Import(ImportStatement),
+    Export(LetStatement),
+    Break(Token),
+    Continue(Token),
 }
 
 impl Display for Statement {
@@ -22,38 +26,66 @@ impl Display for Statement {
             Statement::Return(statement) => write!(f, "{}", statement),
             Statement::Expression(expression) => write!(f, "{}", expression),
             Statement::Source(source) => write!(f, "{}", source),
+            Statement::Import(import) => write!(f, "{}", import),
+            Statement::Export(statement) => write!(f, "Export {}", statement),
+            Statement::Break(_) => write!(f, "break;"),
+            Statement::Continue(_) => write!(f, "continue;"),
         }
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+impl Statement {
+    /// Where the statement starts and ends in the input
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::Assignment(statement) => statement.span.clone(),
+            Statement::Return(statement) => statement.span.clone(),
+            Statement::Expression(expression) => expression.span(),
+            Statement::Source(source) => source.span.clone(),
+            Statement::Import(import) => import.span.clone(),
+            Statement::Export(statement) => statement.span.clone(),
+            Statement::Break(token) | Statement::Continue(token) => token.span.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct LetStatement {
     pub name: IdentNode,
     pub val: Expression,
+    pub span: Span,
 }
 
+eq_without_span!(LetStatement, name, val);
+
 impl Display for LetStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Let => {{ {} | Assigned | {} }}", self.name, self.val)
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct ReturnStatement {
     pub returnable: Expression,
+    pub span: Span,
 }
 
+eq_without_span!(ReturnStatement, returnable);
+
 impl Display for ReturnStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "return {};", self.returnable)
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct BlockStatement {
     pub statements: Vec<Statement>,
+    pub span: Span,
 }
 
+eq_without_span!(BlockStatement, statements);
+
 impl Display for BlockStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut block = String::new();
@@ -66,6 +98,8 @@ impl Display for BlockStatement {
 
 impl BlockStatement {
     pub fn parse(parser: &mut Parser) -> Self {
+        let start = parser.curr_token.span.clone();
+        parser.blocks.push(parser.depth);
         parser.next_token();
 
         let mut block = Vec::<Statement>::new();
@@ -73,24 +107,71 @@ impl BlockStatement {
         while !parser.curr_token_is(&dummy_token(TokenKind::RCurly))
             && !parser.curr_token.reached_eof()
         {
-            if let Some(statement) = parser.parse_statement() {
-                block.push(statement);
+            match parser.parse_statement() {
+                Some(statement) => block.push(statement),
+                None => {
+                    if parser.synchronize() {
+                        break;
+                    }
+                }
             }
 
             parser.next_token();
         }
 
-        BlockStatement { statements: block }
+        parser.blocks.pop();
+
+        BlockStatement {
+            statements: block,
+            span: start.to(&parser.curr_token.span),
+        }
     }
 }
 
+/// Where a sourced file is found. Paths written as string literals
+/// are known while parsing, any other expression has to evaluate to one
 #[derive(PartialEq, Debug, Clone)]
+pub enum SourcePath {
+    Literal(PathBuf),
+    Computed(Expression),
+}
+
+impl Display for SourcePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourcePath::Literal(path) => write!(f, "{}", path.display()),
+            SourcePath::Computed(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct SourceStatement {
-    pub path: PathBuf,
+    pub path: SourcePath,
+    pub span: Span,
 }
 
+eq_without_span!(SourceStatement, path);
+
 impl Display for SourceStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Source {}", self.path.to_str().unwrap())
+        write!(f, "Source {}", self.path)
+    }
+}
+
+/// Evaluates a file in its own scope and binds
+/// the names it exports to a module object
+#[derive(Debug, Clone)]
+pub struct ImportStatement {
+    pub source: SourceStatement,
+    pub alias: IdentNode,
+    pub span: Span,
+}
+
+eq_without_span!(ImportStatement, source, alias);
+
+impl Display for ImportStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Import {} as {}", self.source.path, self.alias)
     }
 }
@@ -0,0 +1,468 @@
+use std::{
+    env,
+    fmt::Display,
+    io::{self, IsTerminal, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use lace_lexer::{token::span::Span, Lexer};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+impl Severity {
+    fn color(&self) -> &'static str {
+        match self {
+            Severity::Error => "\x1b[91m",
+            Severity::Warning => "\x1b[93m",
+        }
+    }
+}
+
+/// Part of the input along with what it has to do with the diagnostic.
+/// The primary label points at the problem, secondary ones add context
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub span: Span,
+    pub text: String,
+    pub primary: bool,
+}
+
+impl Label {
+    pub fn primary(span: Span, text: impl Into<String>) -> Self {
+        Self {
+            span,
+            text: text.into(),
+            primary: true,
+        }
+    }
+
+    pub fn secondary(span: Span, text: impl Into<String>) -> Self {
+        Self {
+            span,
+            text: text.into(),
+            primary: false,
+        }
+    }
+}
+
+/// A fix which can be applied by replacing the span with the replacement.
+/// An empty span inserts the replacement where it starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
+}
+
+/// Everything there is to report about a problem in the input,
+/// shared by the parser's errors and the evaluator's runtime errors
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// stable identifier like `E0012`
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity: Severity::Error,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_suggestion(
+        mut self,
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.suggestions.push(Suggestion {
+            message: message.into(),
+            span,
+            replacement: replacement.into(),
+        });
+        self
+    }
+
+    pub fn primary(&self) -> Option<&Label> {
+        self.labels.iter().find(|label| label.primary)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorChoice {
+    /// Colour output going to a terminal unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "Unknown colour choice '{}'. Expected auto, always or never",
+                s
+            )),
+        }
+    }
+}
+
+impl ColorChoice {
+    pub fn enabled(&self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                is_terminal && env::var_os("NO_COLOR").is_none_or(|val| val.is_empty())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ErrorFormat {
+    /// Snippets of the input with the labels marked
+    #[default]
+    Human,
+    /// One JSON object per line
+    Json,
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(ErrorFormat::Human),
+            "json" => Ok(ErrorFormat::Json),
+            _ => Err(format!(
+                "Unknown error format '{}'. Expected human or json",
+                s
+            )),
+        }
+    }
+}
+
+/// Writes diagnostics out in the chosen format
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Emitter {
+    pub color: bool,
+    pub format: ErrorFormat,
+}
+
+impl Default for Emitter {
+    /// Human readable diagnostics, coloured when standard error is a terminal
+    fn default() -> Self {
+        Self::new(ColorChoice::Auto, ErrorFormat::Human, &io::stderr())
+    }
+}
+
+impl Emitter {
+    /// Emitter for diagnostics written to `stream`, which decides
+    /// whether `ColorChoice::Auto` turns colour on
+    pub fn new(color: ColorChoice, format: ErrorFormat, stream: &impl IsTerminal) -> Self {
+        Self {
+            color: color.enabled(stream.is_terminal()),
+            format,
+        }
+    }
+
+    pub fn emit(
+        &self,
+        out: &mut impl Write,
+        diagnostic: &Diagnostic,
+        lexer: &Lexer,
+        file: Option<&Path>,
+    ) -> io::Result<()> {
+        match self.format {
+            ErrorFormat::Human => self.emit_human(out, diagnostic, lexer, file),
+            ErrorFormat::Json => writeln!(out, "{}", json(diagnostic, file)),
+        }
+    }
+
+    /// Prints the diagnostic with a snippet of the input under each label.
+    /// Labels that don't point into the input are left out
+    fn emit_human(
+        &self,
+        out: &mut impl Write,
+        diagnostic: &Diagnostic,
+        lexer: &Lexer,
+        file: Option<&Path>,
+    ) -> io::Result<()> {
+        let mut labels = diagnostic
+            .labels
+            .iter()
+            .filter(|label| label.span.start_line > 0)
+            .collect::<Vec<&Label>>();
+        labels.sort_by_key(|label| !label.primary);
+
+        let color = diagnostic.severity.color();
+        let head = self.paint(
+            color,
+            &format!("{}[{}]", diagnostic.severity, diagnostic.code),
+        );
+
+        match labels.split_first() {
+            Some((first, rest)) => {
+                self.snippet(out, lexer, file, &head, first, color)?;
+                writeln!(out, "\t{}", diagnostic.message)?;
+                for label in rest {
+                    self.snippet(out, lexer, file, "", label, BLUE)?;
+                }
+            }
+            None => {
+                writeln!(out, "{head}")?;
+                writeln!(out, "\t{}", diagnostic.message)?;
+            }
+        }
+
+        for note in &diagnostic.notes {
+            writeln!(out, "\t{} {note}", self.paint(BLUE, "= note:"))?;
+        }
+        for suggestion in &diagnostic.suggestions {
+            writeln!(
+                out,
+                "\t{} {}",
+                self.paint(GREEN, "= help:"),
+                suggestion.message
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Prints the lines of the input the label spans with markers
+    /// under its columns, preceded by the header and where the label points
+    fn snippet(
+        &self,
+        out: &mut impl Write,
+        lexer: &Lexer,
+        file: Option<&Path>,
+        header: &str,
+        label: &Label,
+        color: &str,
+    ) -> io::Result<()> {
+        let Span {
+            start_line: sline,
+            end_line: eline,
+            start_col: schar,
+            end_col: echar,
+        } = label.span;
+
+        // the last line need not end with a line break
+        let snippet_end = match lexer.line_breaks.get(eline) {
+            Some(brk) => brk - 1,
+            None => lexer.input.len(),
+        };
+        let prn = lexer.input[(lexer.line_breaks[sline - 1])..snippet_end]
+            .iter()
+            .collect::<String>();
+        let marker = if label.primary { "^" } else { "-" };
+        let ptrln = format!(
+            "{}{} {}",
+            " ".repeat(schar),
+            marker.repeat(echar.saturating_sub(schar)),
+            label.text
+        )
+        .trim_end()
+        .to_string();
+        let num_len = (eline.checked_ilog10().unwrap_or(0) + 1) as usize;
+
+        let location = match file {
+            Some(file) => format!(" --> At {}:{} in {}", sline, schar + 1, file.display()),
+            None => format!(" --> At {}:{}", sline, schar + 1),
+        };
+        writeln!(out, "{header}{}", self.paint(BLUE, &location))?;
+        writeln!(out, "{: <1$}{2}", "", num_len, self.paint(BLUE, " | "))?;
+        for (curr_line, line) in (sline..).zip(prn.lines()) {
+            let number = format!("{: >1$} | ", curr_line, num_len);
+            writeln!(out, "{}\t{line}", self.paint(BLUE, &number))?;
+        }
+        writeln!(
+            out,
+            "{: <1$}{2}\t{3}",
+            "",
+            num_len,
+            self.paint(BLUE, " | "),
+            self.paint(color, &ptrln)
+        )
+    }
+
+    fn paint(&self, color: &str, text: &str) -> String {
+        match self.color {
+            true => format!("{color}{text}{RESET}"),
+            false => text.into(),
+        }
+    }
+}
+
+const BLUE: &str = "\x1b[94m";
+const GREEN: &str = "\x1b[92m";
+const RESET: &str = "\x1b[0m";
+
+/// The diagnostic as a single line of JSON.
+/// Lines and columns start at 1 and the end columns are exclusive
+fn json(diagnostic: &Diagnostic, file: Option<&Path>) -> String {
+    let span = |span: &Span| {
+        format!(
+            r#""line":{},"column":{},"end_line":{},"end_column":{}"#,
+            span.start_line,
+            span.start_col + 1,
+            span.end_line,
+            span.end_col + 1
+        )
+    };
+
+    let location = match diagnostic.primary() {
+        Some(label) => span(&label.span),
+        None => r#""line":null,"column":null,"end_line":null,"end_column":null"#.into(),
+    };
+    let file = match file {
+        Some(file) => json_str(&file.display().to_string()),
+        None => "null".into(),
+    };
+    let labels = diagnostic
+        .labels
+        .iter()
+        .map(|label| {
+            format!(
+                r#"{{{},"primary":{},"text":{}}}"#,
+                span(&label.span),
+                label.primary,
+                json_str(&label.text)
+            )
+        })
+        .collect::<Vec<String>>();
+    let notes = diagnostic
+        .notes
+        .iter()
+        .map(|note| json_str(note))
+        .collect::<Vec<String>>();
+    let suggestions = diagnostic
+        .suggestions
+        .iter()
+        .map(|suggestion| {
+            format!(
+                r#"{{{},"message":{},"replacement":{}}}"#,
+                span(&suggestion.span),
+                json_str(&suggestion.message),
+                json_str(&suggestion.replacement)
+            )
+        })
+        .collect::<Vec<String>>();
+
+    format!(
+        r#"{{"code":{},"severity":{},"message":{},"file":{},{},"labels":[{}],"notes":[{}],"suggestions":[{}]}}"#,
+        json_str(diagnostic.code),
+        json_str(&diagnostic.severity.to_string()),
+        json_str(&diagnostic.message),
+        file,
+        location,
+        labels.join(","),
+        notes.join(","),
+        suggestions.join(",")
+    )
+}
+
+fn json_str(s: &str) -> String {
+    let mut escaped = String::from('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+/// Number of insertions, deletions, substitutions and swaps of
+/// neighbouring characters needed to turn one word into the other
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+
+    let mut dist = vec![vec![0; b.len() + 1]; a.len() + 1];
+    (0..=a.len()).for_each(|i| dist[i][0] = i);
+    (0..=b.len()).for_each(|j| dist[0][j] = j);
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            dist[i][j] = (dist[i - 1][j] + 1)
+                .min(dist[i][j - 1] + 1)
+                .min(dist[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                dist[i][j] = dist[i][j].min(dist[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    dist[a.len()][b.len()]
+}
+
+/// Candidates spelled closest to the word, best match first.
+/// Those which would need to be mostly rewritten are left out.
+/// A word may be off by one edit for every three characters, so names
+/// shorter than six characters only match candidates one edit away
+pub fn closest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let max_dist = word.chars().count().max(3) / 3;
+
+    let mut found = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != word)
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|(dist, candidate)| *dist <= max_dist && *dist < candidate.chars().count())
+        .collect::<Vec<(usize, &str)>>();
+    found.sort();
+    found.dedup();
+
+    found.into_iter().take(3).map(|(_, name)| name).collect()
+}
@@ -1,34 +1,11 @@
-use std::{fmt::Display, usize};
+use std::fmt::Display;
 
-use lace_lexer::token::{span::Span, Token};
+use lace_lexer::token::{kind::TokenKind, span::Span, Token};
 
-macro_rules! build_err_head {
-    ($name:tt, $($tok:ident).+) => {
-        fn $name(&self) -> String {
-            format!(
-                "\x1b[94m--> At {}:{}\x1b[0m",
-                self.$($tok).+.span.start_line,
-                self.$($tok).+.span.start_col + 1
-            )
-        }
-    };
-}
-
-macro_rules! build_err_range {
-    ($name:tt, $($tok:ident).+) => {
-        fn $name(&self) -> (usize, usize) {
-            (self.$($tok).+.span.start_line, self.$($tok).+.span.end_line)
-        }
-    };
-}
-
-macro_rules! build_err_width {
-    ($name:tt, $($tok:ident).+) => {
-        fn $name(&self) -> (usize, usize) {
-            (self.$($tok).+.span.start_col, self.$($tok).+.span.end_col)
-        }
-    };
-}
+use crate::{
+    ast::{nodes::PrimitiveNode, Expression},
+    diagnostic::{edit_distance, Diagnostic, Label},
+};
 
 macro_rules! check_end {
     ($e:expr, $($dim:ident).+) => {
@@ -39,21 +16,22 @@ macro_rules! check_end {
     };
 }
 
-// TODO: Add suggestions for some errors
 pub trait ParserError {
-    fn emit_err(&self) -> String;
-    fn range(&self) -> (usize, usize) {
-        (0, 0)
-    }
-    fn width(&self) -> (usize, usize) {
-        (0, 0)
-    }
-    fn err_head(&self) -> String {
-        "".into()
-    }
-    fn check_false_illegal(&self) -> bool {
-        false
-    }
+    fn diagnostic(&self) -> Diagnostic;
+}
+
+/// Points at the whole token
+fn at(token: &Token) -> Label {
+    Label::primary(token.span.clone(), "")
+}
+
+/// The keyword the identifier is most likely a typo of
+pub fn misspelled(found: &Token, keyword: &TokenKind) -> Option<&'static str> {
+    let (TokenKind::Ident { label }, Some(text)) = (&found.kind, keyword.text()) else {
+        return None;
+    };
+
+    (TokenKind::try_keyword(text).is_some() && edit_distance(label, text) == 1).then_some(text)
 }
 
 pub struct NoPrefixParser {
@@ -61,16 +39,16 @@ pub struct NoPrefixParser {
 }
 
 impl ParserError for NoPrefixParser {
-    build_err_head!(err_head, token);
-    build_err_range!(range, token);
-    build_err_width!(width, token);
-
-    fn emit_err(&self) -> String {
-        format!("\tEncountered an illegal token {}", self.token.kind)
-    }
+    fn diagnostic(&self) -> Diagnostic {
+        let message = match self.token.kind {
+            TokenKind::Illegal { ch: _ } => {
+                format!("Encountered an illegal token {}", self.token.kind)
+            }
+            TokenKind::Eof => "Unexpected end of input".into(),
+            _ => format!("Unexpected {}", self.token.kind),
+        };
 
-    fn check_false_illegal(&self) -> bool {
-        self.token.is_actually_legal()
+        Diagnostic::error("E0001", message).with_label(at(&self.token))
     }
 }
 
@@ -103,7 +81,10 @@ impl Display for CondIssue {
                 )
             }
             CondIssue::ExpectedElse => {
-                write!(f, "Expected an Else block for this conditional expression.")
+                write!(
+                    f,
+                    "Expected an Else block or an Else If for this conditional expression."
+                )
             }
         }
     }
@@ -113,48 +94,30 @@ pub struct IncompleteConditional {
     pub start: Span,
     issue: CondIssue,
     pub end: Option<Span>,
-    pub current: usize,
 }
 
 impl IncompleteConditional {
-    pub fn new(start: Span, issue: CondIssue, end: Option<Span>, current: usize) -> Self {
-        Self {
-            start,
-            issue,
-            end,
-            current,
-        }
+    pub fn new(start: Span, issue: CondIssue, end: Option<Span>) -> Self {
+        Self { start, issue, end }
     }
 }
 
 impl ParserError for IncompleteConditional {
-    fn emit_err(&self) -> String {
-        format!("\t{}", self.issue)
-    }
-
-    fn range(&self) -> (usize, usize) {
-        // (self.start.start_line, check_end_line!(&self))
-        (self.start.start_line, check_end!(&self, end_line))
-    }
-
-    fn width(&self) -> (usize, usize) {
-        let start = if let CondIssue::ExprIncorrectlyClosed = self.issue {
+    fn diagnostic(&self) -> Diagnostic {
+        let start_col = if let CondIssue::ExprIncorrectlyClosed = self.issue {
             self.start.start_col
         } else {
-            self.start.start_col - 1
+            self.start.start_col.saturating_sub(1)
         };
 
-        (start, check_end!(&self, end_col))
-        // match self.issue {
-        //     CondIssue::ExprIncorrectlyOpened => (self.start.start_col - 1, check_end_col!(&self)),
-        //     CondIssue::ExprIncorrectlyClosed => (self.start.start_col, check_end_col!(&self)),
-        //     CondIssue::BodyIncorrectlyOpened => (self.start.start_col - 1, check_end_col!(&self)),
-        //     CondIssue::ExpectedElse => (self.start.start_col - 1, check_end_col!(&self)),
-        // }
-    }
+        let span = Span {
+            start_line: self.start.start_line,
+            end_line: check_end!(&self, end_line),
+            start_col,
+            end_col: check_end!(&self, end_col),
+        };
 
-    fn err_head(&self) -> String {
-        format!("\x1b[94m--> At {}:{}\x1b[0m", self.range().1, self.current,)
+        Diagnostic::error("E0008", self.issue.to_string()).with_label(Label::primary(span, ""))
     }
 }
 
@@ -182,65 +145,178 @@ pub struct FuncError {
     pub start: Span,
     issue: FuncIssue,
     pub end: Option<Span>,
-    pub current: usize,
 }
 
 impl FuncError {
-    pub fn new(start: Span, issue: FuncIssue, end: Option<Span>, current: usize) -> Self {
-        Self {
-            start,
-            issue,
-            end,
-            current,
-        }
+    pub fn new(start: Span, issue: FuncIssue, end: Option<Span>) -> Self {
+        Self { start, issue, end }
     }
 }
 
 impl ParserError for FuncError {
-    fn emit_err(&self) -> String {
-        format!("\t{}", self.issue)
+    fn diagnostic(&self) -> Diagnostic {
+        let end_col = match self.issue {
+            FuncIssue::BodyIncorrectlyOpened => check_end!(&self, end_col) + 3,
+            _ => check_end!(&self, end_col),
+        };
+
+        let span = Span {
+            start_line: self.start.start_line,
+            end_line: check_end!(&self, end_line),
+            start_col: self.start.start_col.saturating_sub(1),
+            end_col,
+        };
+
+        Diagnostic::error("E0009", self.issue.to_string()).with_label(Label::primary(span, ""))
+    }
+}
+
+pub enum LoopIssue {
+    ExprIncorrectlyOpened,
+    ExprIncorrectlyClosed,
+    BodyIncorrectlyOpened,
+    ExpectedIn,
+}
+
+impl Display for LoopIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoopIssue::ExprIncorrectlyOpened => {
+                write!(f, "Loop condition didn't open properly. Expected '('")
+            }
+            LoopIssue::ExprIncorrectlyClosed => {
+                write!(f, "Loop condition didn't close properly. Expected ')'")
+            }
+            LoopIssue::BodyIncorrectlyOpened => {
+                write!(f, "Body of loop didn't open properly. Expected '{{'")
+            }
+            LoopIssue::ExpectedIn => {
+                write!(f, "Expected 'in' after the loop variables")
+            }
+        }
+    }
+}
+
+pub struct IncompleteLoop {
+    pub start: Span,
+    issue: LoopIssue,
+    pub end: Option<Span>,
+}
+
+impl IncompleteLoop {
+    pub fn new(start: Span, issue: LoopIssue, end: Option<Span>) -> Self {
+        Self { start, issue, end }
     }
+}
+
+impl ParserError for IncompleteLoop {
+    fn diagnostic(&self) -> Diagnostic {
+        let span = self.start.to(self.end.as_ref().unwrap_or(&self.start));
 
-    fn range(&self) -> (usize, usize) {
-        (self.start.start_line, check_end!(&self, end_line))
+        Diagnostic::error("E0010", self.issue.to_string()).with_label(Label::primary(span, ""))
     }
+}
 
-    fn width(&self) -> (usize, usize) {
-        match self.issue {
-            FuncIssue::FuncMissingParens => (self.start.start_col - 1, check_end!(&self, end_col)),
-            FuncIssue::BodyIncorrectlyOpened => {
-                (self.start.start_col - 1, check_end!(&self, end_col) + 3)
+pub enum MatchIssue {
+    ExprIncorrectlyOpened,
+    ExprIncorrectlyClosed,
+    BodyIncorrectlyOpened,
+    BodyIncorrectlyClosed,
+    NoArms,
+    ExpectedArrow,
+    InvalidPattern,
+}
+
+impl Display for MatchIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchIssue::ExprIncorrectlyOpened => {
+                write!(f, "Matched expression didn't open properly. Expected '('")
             }
-            FuncIssue::DefIncorrectlyClosed => {
-                (self.start.start_col - 1, check_end!(&self, end_col))
+            MatchIssue::ExprIncorrectlyClosed => {
+                write!(f, "Matched expression didn't close properly. Expected ')'")
             }
+            MatchIssue::BodyIncorrectlyOpened => {
+                write!(f, "Body of match didn't open properly. Expected '{{'")
+            }
+            MatchIssue::BodyIncorrectlyClosed => {
+                write!(f, "Body of match wasn't closed. Expected '}}'")
+            }
+            MatchIssue::NoArms => write!(f, "Match needs at least one arm"),
+            MatchIssue::ExpectedArrow => write!(f, "Expected '=>' after the pattern"),
+            MatchIssue::InvalidPattern => write!(
+                f,
+                "Expected a pattern. Patterns are literals, identifiers, '_', arrays or hashmaps"
+            ),
         }
     }
+}
+
+pub struct IncompleteMatch {
+    pub start: Span,
+    issue: MatchIssue,
+    pub end: Option<Span>,
+}
+
+impl IncompleteMatch {
+    pub fn new(start: Span, issue: MatchIssue, end: Option<Span>) -> Self {
+        Self { start, issue, end }
+    }
+}
+
+impl ParserError for IncompleteMatch {
+    fn diagnostic(&self) -> Diagnostic {
+        let span = self.start.to(self.end.as_ref().unwrap_or(&self.start));
 
-    fn err_head(&self) -> String {
-        format!("\x1b[94m--> At {}:{}\x1b[0m", self.range().1, self.current,)
+        Diagnostic::error("E0011", self.issue.to_string()).with_label(Label::primary(span, ""))
     }
 }
 
 pub struct ExprError {
     pub start: Span,
     pub end: Option<Span>,
-    pub current: usize,
 }
 
 impl ExprError {
-    pub fn new(start: Span, end: Option<Span>, current: usize) -> Self {
-        Self {
-            start,
-            end,
-            current,
-        }
+    pub fn new(start: Span, end: Option<Span>) -> Self {
+        Self { start, end }
     }
 }
 
 impl ParserError for ExprError {
-    fn emit_err(&self) -> String {
-        "Expression did not close properly".into()
+    fn diagnostic(&self) -> Diagnostic {
+        let span = match &self.end {
+            Some(end) => self.start.to(end),
+            None => self.start.clone(),
+        };
+
+        Diagnostic::error("E0012", "Expression did not close properly")
+            .with_label(Label::primary(span, "this expression isn't closed"))
+    }
+}
+
+pub struct InvalidAssignment {
+    pub operator: Token,
+    /// `=` in a condition was most likely meant to be `==`,
+    /// whether or not its target could be assigned to
+    pub in_condition: bool,
+}
+
+impl ParserError for InvalidAssignment {
+    fn diagnostic(&self) -> Diagnostic {
+        match self.operator.kind {
+            TokenKind::Assign if self.in_condition => {
+                Diagnostic::error("E0007", "Conditions can't contain an assignment")
+                    .with_label(at(&self.operator))
+                    .with_suggestion("did you mean `==`?", self.operator.span.clone(), "==")
+            }
+            _ => Diagnostic::error(
+                "E0007",
+                "Only variables and their indexes can be assigned to",
+            )
+            .with_label(at(&self.operator))
+            .with_note("targets look like `x` or `x[0]`"),
+        }
     }
 }
 
@@ -255,16 +331,19 @@ impl From<Token> for ExpectedIdent {
 }
 
 impl ParserError for ExpectedIdent {
-    build_err_head!(err_head, found);
-    build_err_range!(width, found);
-    build_err_range!(range, found);
-
-    fn emit_err(&self) -> String {
-        format!("Expected an identifier, received {}", self.found)
-    }
-
-    fn check_false_illegal(&self) -> bool {
-        self.found.is_actually_legal()
+    fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::error(
+            "E0003",
+            format!("Expected an identifier, received {}", self.found.kind),
+        )
+        .with_label(at(&self.found));
+
+        match self.found.kind.text() {
+            Some(keyword) if TokenKind::try_keyword(keyword).is_some() => diagnostic.with_note(
+                format!("`{}` is a keyword and can't be used as a name", keyword),
+            ),
+            _ => diagnostic,
+        }
     }
 }
 
@@ -285,13 +364,17 @@ impl ExpectedNumber {
 }
 
 impl ParserError for ExpectedNumber {
-    fn emit_err(&self) -> String {
+    fn diagnostic(&self) -> Diagnostic {
         let kind = match self.kind {
             NumKind::Int => "an integer",
             NumKind::Float => "a floating point number",
         };
 
-        format!("Expected {}, received {}", kind, self.found)
+        Diagnostic::error(
+            "E0004",
+            format!("Expected {}, received {}", kind, self.found.kind),
+        )
+        .with_label(at(&self.found))
     }
 }
 
@@ -302,23 +385,95 @@ pub enum UnterminatedKind {
 
 pub struct UnterminatedLiteral {
     kind: UnterminatedKind,
+    span: Span,
 }
 
-impl From<UnterminatedKind> for UnterminatedLiteral {
-    fn from(value: UnterminatedKind) -> Self {
-        Self { kind: value }
+impl UnterminatedLiteral {
+    pub fn new(kind: UnterminatedKind, span: Span) -> Self {
+        Self { kind, span }
     }
 }
 
 impl ParserError for UnterminatedLiteral {
-    fn emit_err(&self) -> String {
-        match self.kind {
-            UnterminatedKind::Char => "Unterminated character".into(),
-            UnterminatedKind::Str => "Unterminated string".into(),
+    fn diagnostic(&self) -> Diagnostic {
+        let message = match self.kind {
+            UnterminatedKind::Char => "Unterminated character",
+            UnterminatedKind::Str => "Unterminated string",
+        };
+
+        let closing = match self.kind {
+            UnterminatedKind::Char => "'",
+            UnterminatedKind::Str => "\"",
+        };
+
+        Diagnostic::error("E0005", message).with_label(Label::primary(
+            self.span.clone(),
+            format!("missing a closing {}", closing),
+        ))
+    }
+}
+
+pub struct InvalidEscape {
+    seq: String,
+    span: Span,
+}
+
+impl InvalidEscape {
+    pub fn new(seq: String, span: Span) -> Self {
+        Self { seq, span }
+    }
+}
+
+impl ParserError for InvalidEscape {
+    fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::error("E0006", format!("Invalid escape sequence '{}'", self.seq))
+            .with_label(Label::primary(self.span.clone(), ""))
+            .with_note("valid escapes are \\n, \\t, \\r, \\0, \\\\, \\\", \\' and \\u{XXXX}")
+    }
+}
+
+pub struct InvalidSource {
+    found: &'static str,
+    span: Span,
+}
+
+impl From<&Expression> for InvalidSource {
+    fn from(value: &Expression) -> Self {
+        let found = match value {
+            Expression::Primitive(literal) => match literal.val {
+                PrimitiveNode::IntegerLiteral(_) => "an integer",
+                PrimitiveNode::FloatLiteral(_) => "a float",
+                PrimitiveNode::CharLiteral(_) => "a character",
+                PrimitiveNode::StringLiteral(_) => "a string",
+                PrimitiveNode::BooleanLiteral(_) => "a boolean",
+                PrimitiveNode::NullLiteral => "null",
+            },
+            Expression::Array(_) => "an array",
+            Expression::HashMapLiteral(_) => "a hashmap",
+            Expression::FunctionDef(_) => "a function",
+            _ => "an expression",
+        };
+
+        Self {
+            found,
+            span: value.span(),
         }
     }
 }
 
+impl ParserError for InvalidSource {
+    fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::error(
+            "E0013",
+            format!(
+                "Expected a path to source or an expression evaluating to one, received {}",
+                self.found
+            ),
+        )
+        .with_label(Label::primary(self.span.clone(), ""))
+    }
+}
+
 pub struct BadExpectations {
     expected: Token,
     got: Token,
@@ -331,7 +486,61 @@ impl BadExpectations {
 }
 
 impl ParserError for BadExpectations {
-    fn emit_err(&self) -> String {
-        format!("Expected {}, found {}", self.expected, self.got)
+    fn diagnostic(&self) -> Diagnostic {
+        let expected = match &self.expected.kind {
+            TokenKind::Ident { label: _ } => "an identifier".to_string(),
+            kind => kind.to_string(),
+        };
+
+        let diagnostic = Diagnostic::error(
+            "E0002",
+            format!("Expected {}, found {}", expected, self.got.kind),
+        )
+        .with_label(at(&self.got));
+
+        if let Some(keyword) = misspelled(&self.got, &self.expected.kind) {
+            return diagnostic.with_suggestion(
+                format!("did you mean `{}`?", keyword),
+                self.got.span.clone(),
+                keyword,
+            );
+        }
+
+        match self.expected.kind.text() {
+            Some(text) if TokenKind::try_keyword(text).is_none() => {
+                let span = Span {
+                    end_line: self.got.span.start_line,
+                    end_col: self.got.span.start_col,
+                    ..self.got.span.clone()
+                };
+
+                diagnostic.with_suggestion(format!("insert `{}`", text), span, text)
+            }
+            _ => diagnostic,
+        }
+    }
+}
+
+/// An identifier where a keyword it closely resembles was expected
+pub struct MisspelledKeyword {
+    found: Token,
+    keyword: &'static str,
+}
+
+impl MisspelledKeyword {
+    pub fn new(found: Token, keyword: &'static str) -> Self {
+        Self { found, keyword }
+    }
+}
+
+impl ParserError for MisspelledKeyword {
+    fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::error("E0014", format!("Unexpected {}", self.found.kind))
+            .with_label(at(&self.found))
+            .with_suggestion(
+                format!("did you mean `{}`?", self.keyword),
+                self.found.span.clone(),
+                self.keyword,
+            )
     }
 }
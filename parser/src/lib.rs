@@ -1,18 +1,25 @@
-use std::path::PathBuf;
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
 
-use errors::{BadExpectations, ParserError};
+use diagnostic::Emitter;
+use errors::{BadExpectations, InvalidAssignment, InvalidSource, ParserError};
 use lace_lexer::{
     token::{dummy_token, kind::TokenKind, Token},
     Lexer,
 };
 
 use crate::ast::{
-    nodes::IdentNode,
-    statement::{LetStatement, ReturnStatement, SourceStatement, Statement},
+    nodes::{IdentNode, LiteralNode, PrimitiveNode},
+    statement::{
+        ImportStatement, LetStatement, ReturnStatement, SourcePath, SourceStatement, Statement,
+    },
     Expression, Precedence, Program,
 };
 
 pub mod ast;
+pub mod diagnostic;
 pub mod errors;
 
 #[cfg(test)]
@@ -25,6 +32,15 @@ pub struct Parser {
     pub curr_token: Token,
     pub peeked_token: Token,
     pub errors: Vec<Box<dyn ParserError>>,
+    /// file the input was read from, if any
+    pub file: Option<PathBuf>,
+    /// number of curly braces open at the current token
+    depth: usize,
+    /// depth inside each block being parsed, the innermost one last
+    blocks: Vec<usize>,
+    /// number of blocks open when the innermost `if` or `while`
+    /// condition being parsed started
+    condition: Option<usize>,
 }
 
 impl Parser {
@@ -34,6 +50,10 @@ impl Parser {
             curr_token: dummy_token(TokenKind::Eof),
             peeked_token: dummy_token(TokenKind::Eof),
             errors: Vec::new(),
+            file: None,
+            depth: 0,
+            blocks: Vec::new(),
+            condition: None,
         };
 
         parser.next_token();
@@ -42,17 +62,33 @@ impl Parser {
         parser
     }
 
+    pub fn for_file(lexer: Lexer, file: PathBuf) -> Self {
+        let mut parser = Self::new(lexer);
+        parser.file = Some(file);
+
+        parser
+    }
+
     pub fn next_token(&mut self) {
         self.curr_token = self.peeked_token.clone();
         self.peeked_token = self.lexer.next_token();
+
+        match self.curr_token.kind {
+            TokenKind::LCurly => self.depth += 1,
+            TokenKind::RCurly => self.depth = self.depth.saturating_sub(1),
+            _ => (),
+        }
     }
 
     pub fn parse_program(&mut self) -> Program {
         let mut program = Program::default();
 
         while !self.curr_token.reached_eof() {
-            if let Some(statememt) = self.parse_statement() {
-                program.statements.push(statememt);
+            match self.parse_statement() {
+                Some(statememt) => program.statements.push(statememt),
+                None => {
+                    self.synchronize();
+                }
             }
 
             self.next_token();
@@ -66,11 +102,15 @@ impl Parser {
             TokenKind::Let => self.parse_let().map(Statement::Assignment),
             TokenKind::Return => self.parse_return().map(Statement::Return),
             TokenKind::Source => self.parse_source().map(Statement::Source),
+            TokenKind::Import => self.parse_import().map(Statement::Import),
+            TokenKind::Export => self.parse_export().map(Statement::Export),
+            TokenKind::Break | TokenKind::Continue => Some(self.parse_loop_control()),
             _ => self.parse_expression().map(Statement::Expression),
         }
     }
 
     pub fn parse_let(&mut self) -> Option<LetStatement> {
+        let start = self.curr_token.span.clone();
         if !self.expect_peek(&dummy_token(TokenKind::Ident {
             label: String::new(),
         })) {
@@ -100,17 +140,22 @@ impl Parser {
         };
 
         if let Expression::FunctionDef(literal) = &mut val {
-            literal.name = Some(name.token.to_string());
+            literal.name.get_or_insert_with(|| name.label.clone());
         };
 
         if self.peek_token_is(&TokenKind::Semicolon) {
             self.next_token();
         }
 
-        Some(LetStatement { name, val })
+        Some(LetStatement {
+            name,
+            val,
+            span: start.to(&self.curr_token.span),
+        })
     }
 
     fn parse_return(&mut self) -> Option<ReturnStatement> {
+        let start = self.curr_token.span.clone();
         self.next_token();
 
         let return_val = match Expression::parse(self, Precedence::Lowest) {
@@ -127,23 +172,29 @@ impl Parser {
 
         Some(ReturnStatement {
             returnable: return_val,
+            span: start.to(&self.curr_token.span),
         })
     }
 
     fn parse_source(&mut self) -> Option<SourceStatement> {
+        let start = self.curr_token.span.clone();
         self.next_token();
 
-        let sourceable = match Expression::parse(self, Precedence::Lowest) {
-            Ok(e) => match e {
-                Expression::Primitive(p) => match p {
-                    ast::nodes::PrimitiveNode::IntegerLiteral(_) => todo!(),
-                    ast::nodes::PrimitiveNode::FloatLiteral(_) => todo!(),
-                    ast::nodes::PrimitiveNode::CharLiteral(_) => todo!(),
-                    ast::nodes::PrimitiveNode::StringLiteral(s) => s,
-                    ast::nodes::PrimitiveNode::BooleanLiteral(_) => todo!(),
-                },
-                _ => todo!(),
-            },
+        let path = match Expression::parse(self, Precedence::Lowest) {
+            Ok(Expression::Primitive(LiteralNode {
+                val: PrimitiveNode::StringLiteral(path),
+                span: _,
+            })) => SourcePath::Literal(PathBuf::from(path)),
+            Ok(
+                expr @ (Expression::Primitive(_)
+                | Expression::Array(_)
+                | Expression::HashMapLiteral(_)
+                | Expression::FunctionDef(_)),
+            ) => {
+                self.found_err(Box::new(InvalidSource::from(&expr)));
+                return None;
+            }
+            Ok(expr) => SourcePath::Computed(expr),
             Err(err) => {
                 self.found_err(err);
                 return None;
@@ -154,9 +205,68 @@ impl Parser {
             self.next_token();
         }
 
-        let path = PathBuf::from(&sourceable);
+        Some(SourceStatement {
+            path,
+            span: start.to(&self.curr_token.span),
+        })
+    }
+
+    fn parse_import(&mut self) -> Option<ImportStatement> {
+        let source = self.parse_source()?;
+
+        if !self.expect_peek(&dummy_token(TokenKind::As)) {
+            return None;
+        }
+
+        if !self.expect_peek(&dummy_token(TokenKind::Ident {
+            label: String::new(),
+        })) {
+            return None;
+        }
+
+        let alias = match IdentNode::parse(self) {
+            Ok(alias) => alias,
+            Err(err) => {
+                self.found_err(err);
+                return None;
+            }
+        };
+
+        if self.peek_token_is(&TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        Some(ImportStatement {
+            span: source.span.to(&self.curr_token.span),
+            source,
+            alias,
+        })
+    }
+
+    fn parse_export(&mut self) -> Option<LetStatement> {
+        let start = self.curr_token.span.clone();
+        if !self.expect_peek(&dummy_token(TokenKind::Let)) {
+            return None;
+        }
+
+        let mut statement = self.parse_let()?;
+        statement.span = start.to(&statement.span);
+
+        Some(statement)
+    }
+
+    fn parse_loop_control(&mut self) -> Statement {
+        let statement = match self.curr_token.kind {
+            TokenKind::Break => Statement::Break(self.curr_token.clone()),
+            TokenKind::Continue => Statement::Continue(self.curr_token.clone()),
+            _ => unreachable!("only called on break or continue"),
+        };
 
-        Some(SourceStatement { path })
+        if self.peek_token_is(&TokenKind::Semicolon) {
+            self.next_token();
+        }
+
+        statement
     }
 
     fn parse_expression(&mut self) -> Option<Expression> {
@@ -183,12 +293,90 @@ impl Parser {
         Precedence::from(&self.peeked_token)
     }
 
+    /// Parses the condition of an `if` or a `while`.
+    /// A plain `=` as the whole condition is rejected, since it's almost
+    /// always a comparison missing its second `=`
+    pub fn parse_condition(&mut self) -> ParserResult<Expression> {
+        let outer = self.condition.replace(self.blocks.len());
+        let cond = Expression::parse(self, Precedence::Lowest);
+        self.condition = outer;
+
+        match cond? {
+            Expression::Assign(assign) if assign.operator.kind == TokenKind::Assign => {
+                Err(Box::new(InvalidAssignment {
+                    operator: assign.operator,
+                    in_condition: true,
+                }))
+            }
+            cond => Ok(cond),
+        }
+    }
+
+    /// Whether the current token is part of a condition
+    /// rather than a block nested inside it
+    pub fn in_condition(&self) -> bool {
+        self.condition == Some(self.blocks.len())
+    }
+
+    /// Skips the rest of a statement which failed to parse so that
+    /// the next call to `next_token` moves to the start of the following one.
+    /// Returns true when the failure was caused by the brace closing
+    /// the enclosing block, which is then the current token
+    fn synchronize(&mut self) -> bool {
+        let depth = self.blocks.last().copied().unwrap_or(0);
+
+        loop {
+            if self.curr_token.reached_eof() || self.peeked_token.reached_eof() {
+                return false;
+            }
+
+            if self.depth < depth {
+                return true;
+            }
+
+            if self.depth == depth {
+                if self.curr_token.kind == TokenKind::Semicolon {
+                    return false;
+                }
+
+                match self.peeked_token.kind {
+                    TokenKind::RCurly if depth > 0 => return false,
+                    TokenKind::Let
+                    | TokenKind::Return
+                    | TokenKind::Source
+                    | TokenKind::Import
+                    | TokenKind::Export
+                    | TokenKind::Break
+                    | TokenKind::Continue
+                    | TokenKind::If
+                    | TokenKind::While
+                    | TokenKind::For
+                    | TokenKind::Match => return false,
+                    _ => (),
+                }
+            }
+
+            self.next_token();
+        }
+    }
+
     fn expect_peek(&mut self, token: &Token) -> bool {
+        if self.accept_peek(token) {
+            true
+        } else {
+            self.peek_error(token);
+            false
+        }
+    }
+
+    /// Moves to the peeked token if it is the one expected.
+    /// Unlike `expect_peek` nothing is reported when it isn't,
+    /// which is left to callers that have a more specific error
+    fn accept_peek(&mut self, token: &Token) -> bool {
         if self.peek_token_is(&token.kind) {
             self.next_token();
             true
         } else {
-            self.peek_error(token);
             false
         }
     }
@@ -204,56 +392,25 @@ impl Parser {
     }
 
     fn peek_error(&mut self, token: &Token) {
-        self.found_err(Box::new(BadExpectations::new(
+        let err = self.unexpected_peek(token);
+        self.found_err(err)
+    }
+
+    fn unexpected_peek(&self, token: &Token) -> Box<BadExpectations> {
+        Box::new(BadExpectations::new(
             token.clone(),
             self.peeked_token.clone(),
-        )))
+        ))
     }
 
     pub fn log_errors(&self) {
-        self.errors.iter().for_each(|err| {
-            if !err.check_false_illegal() {
-                let header = err.err_head();
-                let (schar, echar) = err.width();
-                let (sline, eline) = err.range();
-                if sline == 0 {
-                    return;
-                }
+        // nothing sensible can be done when standard output is gone
+        let _ = self.emit_errors(&mut io::stdout(), &Emitter::default());
+    }
 
-                let prn = self.lexer.input
-                    [(self.lexer.line_breaks[sline - 1])..(self.lexer.line_breaks[eline] - 1)]
-                    .iter()
-                    .collect::<String>();
-                let width = prn.len();
-                let mut ptrln = String::new();
-                for _ in 0..schar {
-                    ptrln.push(' ');
-                }
-                for _ in schar..echar {
-                    ptrln.push('^');
-                }
-                for _ in (echar)..width {
-                    ptrln.push(' ');
-                }
-                let mut curr_line = sline;
-                let num_len = eline.checked_ilog10().unwrap_or(0) + 1;
-
-                println!("{header}");
-                println!("{: <1$}\x1b[94m | \x1b[0m", "", num_len as usize);
-                prn.lines().for_each(|line| {
-                    let spaces = num_len - (curr_line.checked_ilog10().unwrap_or(0) + 1);
-                    println!(
-                        "{: <1$}\x1b[94m{curr_line} | \x1b[0m\t{line}",
-                        "", spaces as usize
-                    );
-                    curr_line += 1;
-                });
-                println!(
-                    "{: <1$}\x1b[94m | \x1b[0m\t\x1b[91m{ptrln}\x1b[0m",
-                    "", num_len as usize
-                );
-                println!("{}", err.emit_err());
-            }
+    pub fn emit_errors(&self, out: &mut impl Write, emitter: &Emitter) -> io::Result<()> {
+        self.errors.iter().try_for_each(|err| {
+            emitter.emit(out, &err.diagnostic(), &self.lexer, self.file.as_deref())
         })
     }
 
@@ -1,16 +1,23 @@
-use std::path::PathBuf;
+use std::{env, fs::File, io, path::PathBuf};
 
 use lace_lexer::{
-    token::{dummy_token, kind::TokenKind},
+    token::{
+        dummy_token,
+        kind::TokenKind,
+        span::{dummy_span, Span},
+    },
     Lexer,
 };
 
 use crate::{
     ast::{
-        nodes::{IdentNode, PrimitiveNode},
-        statement::{LetStatement, SourceStatement, Statement},
+        nodes::{Assignment, IdentNode, LiteralNode, PrimitiveNode, WhileLoop},
+        statement::{
+            BlockStatement, ImportStatement, LetStatement, SourcePath, SourceStatement, Statement,
+        },
         Expression,
     },
+    diagnostic::{closest, ColorChoice, Emitter, ErrorFormat},
     Parser,
 };
 
@@ -29,6 +36,13 @@ pub fn validate_parser(input: &str, expected_statemets: Vec<Statement>) {
     }
 }
 
+fn literal(val: PrimitiveNode) -> Expression {
+    Expression::Primitive(LiteralNode {
+        val,
+        span: dummy_span(),
+    })
+}
+
 fn check_parser_errors(parser: &Parser) -> usize {
     if !parser.errors.is_empty() {
         println!("Parser has {} errors:", parser.errors.len());
@@ -54,14 +68,16 @@ fn will_you_parse_let() {
                 token: dummy_token(TokenKind::Ident { label: "x".into() }),
                 label: "x".to_string(),
             },
-            val: Expression::Primitive(PrimitiveNode::IntegerLiteral(5)),
+            val: literal(PrimitiveNode::IntegerLiteral(5)),
+            span: dummy_span(),
         }),
         Statement::Assignment(LetStatement {
             name: IdentNode {
                 token: dummy_token(TokenKind::Ident { label: "y".into() }),
                 label: "y".to_string(),
             },
-            val: Expression::Primitive(PrimitiveNode::IntegerLiteral(10)),
+            val: literal(PrimitiveNode::IntegerLiteral(10)),
+            span: dummy_span(),
         }),
         Statement::Assignment(LetStatement {
             name: IdentNode {
@@ -70,7 +86,8 @@ fn will_you_parse_let() {
                 }),
                 label: "flag".to_string(),
             },
-            val: Expression::Primitive(PrimitiveNode::BooleanLiteral(false)),
+            val: literal(PrimitiveNode::BooleanLiteral(false)),
+            span: dummy_span(),
         }),
         Statement::Assignment(LetStatement {
             name: IdentNode {
@@ -85,6 +102,7 @@ fn will_you_parse_let() {
                 }),
                 label: "y".to_string(),
             }),
+            span: dummy_span(),
         }),
     ];
 
@@ -121,19 +139,1111 @@ fn will_you_parse_source() {
                 token: dummy_token(TokenKind::Ident { label: "x".into() }),
                 label: "x".to_string(),
             },
-            val: Expression::Primitive(PrimitiveNode::IntegerLiteral(5)),
+            val: literal(PrimitiveNode::IntegerLiteral(5)),
+            span: dummy_span(),
         }),
         Statement::Source(SourceStatement {
-            path: PathBuf::from("path/to/source"),
+            path: SourcePath::Literal(PathBuf::from("path/to/source")),
+            span: dummy_span(),
         }),
         Statement::Assignment(LetStatement {
             name: IdentNode {
                 token: dummy_token(TokenKind::Ident { label: "y".into() }),
                 label: "y".to_string(),
             },
-            val: Expression::Primitive(PrimitiveNode::IntegerLiteral(10)),
+            val: literal(PrimitiveNode::IntegerLiteral(10)),
+            span: dummy_span(),
         }),
     ];
 
     validate_parser(input, expected_statemets)
 }
+
+#[test]
+fn will_you_parse_imports() {
+    let input = r#"
+        import "lib/math" as math;
+        export let x = 5;
+    "#;
+    let expected_statemets = vec![
+        Statement::Import(ImportStatement {
+            source: SourceStatement {
+                path: SourcePath::Literal(PathBuf::from("lib/math")),
+                span: dummy_span(),
+            },
+            alias: IdentNode {
+                token: dummy_token(TokenKind::Ident {
+                    label: "math".into(),
+                }),
+                label: "math".to_string(),
+            },
+            span: dummy_span(),
+        }),
+        Statement::Export(LetStatement {
+            name: IdentNode {
+                token: dummy_token(TokenKind::Ident { label: "x".into() }),
+                label: "x".to_string(),
+            },
+            val: literal(PrimitiveNode::IntegerLiteral(5)),
+            span: dummy_span(),
+        }),
+    ];
+
+    validate_parser(input, expected_statemets)
+}
+
+#[test]
+fn detect_invalid_escape() {
+    let input = r#"let s = "bad \q escape";"#;
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    parser.parse_program();
+
+    assert_eq!(parser.errors.len(), 1);
+    assert_eq!(
+        parser.errors[0].diagnostic().message,
+        "Invalid escape sequence '\\q'"
+    );
+    let span = &parser.errors[0].diagnostic().labels[0].span;
+    assert_eq!((span.start_col, span.end_col), (13, 15));
+}
+
+#[test]
+fn will_you_parse_numbers() {
+    let input = "0xff; 0o17; 0b1010; 1_000_000; 1.5e-3;";
+
+    let expected_statemets = vec![
+        Statement::Expression(literal(PrimitiveNode::IntegerLiteral(255))),
+        Statement::Expression(literal(PrimitiveNode::IntegerLiteral(15))),
+        Statement::Expression(literal(PrimitiveNode::IntegerLiteral(10))),
+        Statement::Expression(literal(PrimitiveNode::IntegerLiteral(1_000_000))),
+        Statement::Expression(literal(PrimitiveNode::FloatLiteral(1.5e-3))),
+    ];
+
+    validate_parser(input, expected_statemets)
+}
+
+#[test]
+fn detect_malformed_numbers() {
+    let input = "1.2.3; 0xZZ; 0b102; 1e;";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    parser.parse_program();
+
+    let errors = parser
+        .errors
+        .iter()
+        .map(|err| err.diagnostic().message)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        vec![
+            "Expected a floating point number, received Float Literal(1.2.3)",
+            "Expected an integer, received Hex Int Literal(ZZ)",
+            "Expected an integer, received Binary Int Literal(102)",
+            "Expected a floating point number, received Float Literal(1e)",
+        ]
+    );
+}
+
+#[test]
+fn will_you_parse_while() {
+    let input = r#"
+        while (true) {
+            break;
+            continue
+        }
+    "#;
+
+    let expected_statemets = vec![Statement::Expression(Expression::While(WhileLoop {
+        cond: Box::new(literal(PrimitiveNode::BooleanLiteral(true))),
+        body: BlockStatement {
+            statements: vec![
+                Statement::Break(dummy_token(TokenKind::Break)),
+                Statement::Continue(dummy_token(TokenKind::Continue)),
+            ],
+            span: dummy_span(),
+        },
+        span: dummy_span(),
+    }))];
+
+    validate_parser(input, expected_statemets)
+}
+
+#[test]
+fn will_you_parse_assignment() {
+    let input = "x = y += 2;";
+
+    let ident = |label: &str| IdentNode {
+        token: dummy_token(TokenKind::Ident {
+            label: label.into(),
+        }),
+        label: label.into(),
+    };
+
+    let expected_statemets = vec![Statement::Expression(Expression::Assign(Assignment {
+        target: Box::new(Expression::Identifier(ident("x"))),
+        operator: dummy_token(TokenKind::Assign),
+        val: Box::new(Expression::Assign(Assignment {
+            target: Box::new(Expression::Identifier(ident("y"))),
+            operator: dummy_token(TokenKind::PlusAssign),
+            val: Box::new(literal(PrimitiveNode::IntegerLiteral(2))),
+            span: dummy_span(),
+        })),
+        span: dummy_span(),
+    }))];
+
+    validate_parser(input, expected_statemets)
+}
+
+#[test]
+fn detect_invalid_assignment() {
+    let input = "x + 1 = 2;";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    parser.parse_program();
+
+    assert_eq!(parser.errors.len(), 1);
+    assert_eq!(
+        parser.errors[0].diagnostic().message,
+        "Only variables and their indexes can be assigned to"
+    );
+
+    for input in [
+        "x + 1 = 2;",
+        "a?[0] = 2;",
+        "x.y = 3;",
+        "if (f(fn() { x.y = 3 })) { 1 }",
+    ] {
+        let lexer = Lexer::new(input.into());
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        let diagnostic = parser.errors[0].diagnostic();
+        assert!(diagnostic.suggestions.is_empty(), "{}", input);
+        assert_eq!(diagnostic.notes, vec!["targets look like `x` or `x[0]`"]);
+    }
+
+    // only a plain `=` is taken for a mistyped comparison
+    for input in ["while (x += 1) { 1 }", "if ((x = 3) == 3) { 1 }"] {
+        let lexer = Lexer::new(input.into());
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        assert_eq!(check_parser_errors(&parser), 0, "{}", input);
+    }
+}
+
+#[test]
+fn detect_unclosed_literals_and_expressions() {
+    let cases = [
+        ("let s = \"abc", "E0005", (1, 8, 11)),
+        ("let c = 'a", "E0005", (1, 8, 10)),
+        ("let x = (1 + 2;", "E0012", (1, 8, 14)),
+        ("f(1, 2;", "E0012", (1, 2, 6)),
+    ];
+
+    for (input, code, (line, start, end)) in cases {
+        let lexer = Lexer::new(input.into());
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        let diagnostic = parser.errors[0].diagnostic();
+        let label = diagnostic.primary().expect(input);
+        assert_eq!(diagnostic.code, code, "{}", input);
+        assert_eq!(
+            (
+                label.span.start_line,
+                label.span.start_col,
+                label.span.end_col
+            ),
+            (line, start, end),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn detect_unassignable_index() {
+    for input in ["f()[0] = 2;", "a?[0] = 2;", "a?[0][1] = 2;"] {
+        let lexer = Lexer::new(input.into());
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        assert_eq!(parser.errors.len(), 1, "{}", input);
+    }
+}
+
+#[test]
+fn will_you_parse_optional_chaining() {
+    let input = "a?.b(c)?[0];";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    assert_eq!(check_parser_errors(&parser), 0);
+    assert_eq!(
+        program.statements[0].to_string(),
+        "Array => Fn Call Object => Ident 'a' | Member? => Ident 'b' => params { Ident 'c' } | Index? => 0 (Int)"
+    );
+}
+
+#[test]
+fn will_you_parse_member_access() {
+    let input = "a.b(c)[0];";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    assert_eq!(check_parser_errors(&parser), 0);
+    assert_eq!(
+        program.statements[0].to_string(),
+        "Array => Fn Call Object => Ident 'a' | Member => Ident 'b' => params { Ident 'c' } | Index => 0 (Int)"
+    );
+}
+
+#[test]
+fn detect_malformed_else_if() {
+    let input = r#"
+        if (a) {
+            1
+        } else if (b) {
+            2
+        } else if c) {
+            3
+        }
+    "#;
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    parser.parse_program();
+
+    let err = parser
+        .errors
+        .iter()
+        .find(|err| {
+            err.diagnostic().message == "Conditional expression didn't open properly. Expected '('"
+        })
+        .expect("malformed branch should be reported");
+    assert_eq!(err.diagnostic().labels[0].span.start_line, 6);
+}
+
+#[test]
+fn detect_armless_match() {
+    let input = "match (x) { }";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    parser.parse_program();
+
+    assert_eq!(parser.errors.len(), 1);
+    assert_eq!(
+        parser.errors[0].diagnostic().message,
+        "Match needs at least one arm"
+    );
+}
+
+#[test]
+fn will_you_parse_patterns() {
+    let input = r#"match (x) { [a, ..rest] if a => 1, { "k": _ } => { 2 } }"#;
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    assert_eq!(check_parser_errors(&parser), 0);
+    assert_eq!(
+        program.statements[0].to_string(),
+        "Match => { value => Ident 'x' | arms => {\n\
+        [Ident 'a', ..Ident 'rest'] if Ident 'a' => {\n1 (Int)\n},\n\
+        { \"k\" (Str): _ } => {\n2 (Int)\n}\n} \n}"
+    );
+}
+
+#[test]
+fn will_you_track_spans() {
+    let input = "let xs = [1, f(2)];\nif (x) {\n    y\n} else { -z }";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    assert_eq!(check_parser_errors(&parser), 0);
+
+    let bounds = |span: Span| (span.start_line, span.start_col, span.end_line, span.end_col);
+
+    let Statement::Assignment(let_statement) = &program.statements[0] else {
+        panic!("expected a let statement");
+    };
+    assert_eq!(bounds(let_statement.span.clone()), (1, 0, 1, 19));
+
+    let Expression::Array(arr) = &let_statement.val else {
+        panic!("expected an array");
+    };
+    assert_eq!(bounds(arr.span.clone()), (1, 9, 1, 18));
+    assert_eq!(bounds(arr.elements[1].span()), (1, 13, 1, 17));
+
+    let Statement::Expression(Expression::Conditional(cond)) = &program.statements[1] else {
+        panic!("expected a conditional");
+    };
+    assert_eq!(bounds(cond.span.clone()), (2, 0, 4, 13));
+    assert_eq!(bounds(cond.consequence.span.clone()), (2, 7, 4, 1));
+    assert_eq!(
+        bounds(cond.alternative.as_ref().unwrap().statements[0].span()),
+        (4, 9, 4, 11)
+    );
+}
+
+#[test]
+fn will_you_recover_from_errors() {
+    let input = r#"
+        let = 5;
+        let f = fn(x) {
+            let y = x + ;
+            y
+        };
+        if (f(1) { 2 }
+        let g = fn(1) { 1 };
+        let ok = [1, 2];
+    "#;
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    let errors = parser
+        .errors
+        .iter()
+        .map(|err| {
+            let diagnostic = err.diagnostic();
+            (diagnostic.code, diagnostic.labels[0].span.start_line)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![("E0002", 2), ("E0001", 4), ("E0008", 7), ("E0003", 8)]
+    );
+
+    let names = program
+        .statements
+        .iter()
+        .map(|statement| match statement {
+            Statement::Assignment(let_statement) => let_statement.name.label.as_str(),
+            _ => "",
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["f", "ok"]);
+}
+
+#[test]
+fn detect_invalid_source() {
+    let input = "source 5; source dir + \"/lib\"; source true; source [\"a\"];";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    let errors = parser
+        .errors
+        .iter()
+        .map(|err| {
+            let diagnostic = err.diagnostic();
+            let span = &diagnostic.labels[0].span;
+            (diagnostic.message, (span.start_col, span.end_col))
+        })
+        .collect::<Vec<_>>();
+    let expected = |found: &str, width| {
+        (
+            format!(
+                "Expected a path to source or an expression evaluating to one, received {}",
+                found
+            ),
+            width,
+        )
+    };
+    assert_eq!(
+        errors,
+        vec![
+            expected("an integer", (7, 8)),
+            expected("a boolean", (38, 42)),
+            expected("an array", (51, 56)),
+        ]
+    );
+
+    assert_eq!(program.statements.len(), 1);
+    assert!(matches!(
+        &program.statements[0],
+        Statement::Source(SourceStatement {
+            path: SourcePath::Computed(Expression::Binary(_)),
+            span: _,
+        })
+    ));
+}
+
+#[test]
+fn will_you_suggest_fixes() {
+    let input =
+        "if (a) { 1 } esle { 2 };\nimport \"m\" sa m;\nwhile (x + 1 = 2) { 3 }\nif (x = 3) { 4 }";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    parser.parse_program();
+
+    let suggestions = parser
+        .errors
+        .iter()
+        .map(|err| {
+            let diagnostic = err.diagnostic();
+            let suggestion = diagnostic.suggestions[0].clone();
+            let span = suggestion.span;
+            (
+                diagnostic.code,
+                suggestion.message,
+                suggestion.replacement,
+                (span.start_line, span.start_col, span.end_col),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        suggestions,
+        vec![
+            (
+                "E0014",
+                "did you mean `else`?".into(),
+                "else".into(),
+                (1, 13, 17)
+            ),
+            (
+                "E0002",
+                "did you mean `as`?".into(),
+                "as".into(),
+                (2, 11, 13)
+            ),
+            (
+                "E0007",
+                "did you mean `==`?".into(),
+                "==".into(),
+                (3, 13, 14)
+            ),
+            ("E0007", "did you mean `==`?".into(), "==".into(), (4, 6, 7)),
+        ]
+    );
+}
+
+#[test]
+fn will_you_find_close_names() {
+    let names = ["count", "counter", "int", "len", "value", "x"];
+
+    assert_eq!(closest("cuont", names), vec!["count"]);
+    assert_eq!(closest("cont", names), vec!["count"]);
+    assert_eq!(closest("lne", names), vec!["len"]);
+    assert_eq!(closest("vlaue", names), vec!["value"]);
+    assert_eq!(closest("countre", names), vec!["counter", "count"]);
+    assert_eq!(closest("y", names), Vec::<&str>::new());
+    assert_eq!(closest("countr", names), vec!["count", "counter"]);
+    assert_eq!(closest("zzzzzz", names), Vec::<&str>::new());
+}
+
+#[test]
+fn will_you_emit_diagnostics() {
+    let input = "let x = 1;\nlet = 2;";
+    let lexer = Lexer::new(input.into());
+    let mut parser = Parser::new(lexer);
+    parser.parse_program();
+
+    let emit = |emitter: Emitter| {
+        let mut out = Vec::new();
+        parser.emit_errors(&mut out, &emitter).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    assert_eq!(
+        emit(Emitter::new(ColorChoice::Never, ErrorFormat::Human, &io::stderr())),
+        "error[E0002] --> At 2:5\n  | \n2 | \tlet = 2;\n  | \t    ^\n\tExpected an identifier, found Assign\n"
+    );
+    assert!(emit(Emitter::new(
+        ColorChoice::Always,
+        ErrorFormat::Human,
+        &io::stderr()
+    ))
+    .contains("\x1b[91m"));
+    assert_eq!(
+        emit(Emitter::new(ColorChoice::Never, ErrorFormat::Json, &io::stderr())),
+        "{\"code\":\"E0002\",\"severity\":\"error\",\"message\":\"Expected an identifier, found Assign\",\
+        \"file\":null,\"line\":2,\"column\":5,\"end_line\":2,\"end_column\":6,\
+        \"labels\":[{\"line\":2,\"column\":5,\"end_line\":2,\"end_column\":6,\"primary\":true,\"text\":\"\"}],\
+        \"notes\":[],\"suggestions\":[]}\n"
+    );
+
+    // auto-detection looks at the stream the emitter writes to
+    let file = File::create(env::temp_dir().join("lace-emitter-auto.txt")).unwrap();
+    assert!(!Emitter::new(ColorChoice::Auto, ErrorFormat::Human, &file).color);
+
+    assert!(!ColorChoice::Auto.enabled(false));
+    assert!(ColorChoice::Always.enabled(false));
+    assert!(!ColorChoice::Never.enabled(true));
+    assert!("sometimes".parse::<ColorChoice>().is_err());
+}
+
+/// Renders the expression with every operation in parentheses
+fn grouped(expr: &Expression) -> String {
+    match expr {
+        Expression::Binary(infix) => format!(
+            "({} {} {})",
+            grouped(&infix.left_expr),
+            infix.operator.kind.text().unwrap(),
+            grouped(&infix.right_expr)
+        ),
+        Expression::Unary(prefix) => format!(
+            "({}{})",
+            prefix.operator.kind.text().unwrap(),
+            grouped(&prefix.right_expr)
+        ),
+        Expression::Assign(assignment) => format!(
+            "({} {} {})",
+            grouped(&assignment.target),
+            assignment.operator.kind.text().unwrap(),
+            grouped(&assignment.val)
+        ),
+        Expression::ArrIndex(index) => format!(
+            "({}{}{}])",
+            grouped(&index.arr),
+            if index.optional { "?[" } else { "[" },
+            grouped(&index.index)
+        ),
+        Expression::Member(member) => format!(
+            "({}{}{})",
+            grouped(&member.object),
+            if member.optional { "?." } else { "." },
+            member.member.label
+        ),
+        Expression::Identifier(ident) => ident.label.clone(),
+        Expression::Primitive(literal) => match literal.val {
+            PrimitiveNode::IntegerLiteral(i) => i.to_string(),
+            _ => literal.to_string(),
+        },
+        _ => expr.to_string(),
+    }
+}
+
+/// Every pair of binary operators, followed by how they mix
+/// with prefix operators and assignments
+const PRECEDENCE_GOLDEN: &str = "\
+a ?? b ?? c => (a ?? (b ?? c))
+a ?? b || c => (a ?? (b || c))
+a ?? b && c => (a ?? (b && c))
+a ?? b == c => (a ?? (b == c))
+a ?? b != c => (a ?? (b != c))
+a ?? b < c => (a ?? (b < c))
+a ?? b <= c => (a ?? (b <= c))
+a ?? b > c => (a ?? (b > c))
+a ?? b >= c => (a ?? (b >= c))
+a ?? b .. c => (a ?? (b .. c))
+a ?? b | c => (a ?? (b | c))
+a ?? b ^ c => (a ?? (b ^ c))
+a ?? b & c => (a ?? (b & c))
+a ?? b << c => (a ?? (b << c))
+a ?? b >> c => (a ?? (b >> c))
+a ?? b + c => (a ?? (b + c))
+a ?? b - c => (a ?? (b - c))
+a ?? b * c => (a ?? (b * c))
+a ?? b / c => (a ?? (b / c))
+a ?? b % c => (a ?? (b % c))
+a ?? b ~/ c => (a ?? (b ~/ c))
+a ?? b ** c => (a ?? (b ** c))
+a || b ?? c => ((a || b) ?? c)
+a || b || c => ((a || b) || c)
+a || b && c => (a || (b && c))
+a || b == c => (a || (b == c))
+a || b != c => (a || (b != c))
+a || b < c => (a || (b < c))
+a || b <= c => (a || (b <= c))
+a || b > c => (a || (b > c))
+a || b >= c => (a || (b >= c))
+a || b .. c => (a || (b .. c))
+a || b | c => (a || (b | c))
+a || b ^ c => (a || (b ^ c))
+a || b & c => (a || (b & c))
+a || b << c => (a || (b << c))
+a || b >> c => (a || (b >> c))
+a || b + c => (a || (b + c))
+a || b - c => (a || (b - c))
+a || b * c => (a || (b * c))
+a || b / c => (a || (b / c))
+a || b % c => (a || (b % c))
+a || b ~/ c => (a || (b ~/ c))
+a || b ** c => (a || (b ** c))
+a && b ?? c => ((a && b) ?? c)
+a && b || c => ((a && b) || c)
+a && b && c => ((a && b) && c)
+a && b == c => (a && (b == c))
+a && b != c => (a && (b != c))
+a && b < c => (a && (b < c))
+a && b <= c => (a && (b <= c))
+a && b > c => (a && (b > c))
+a && b >= c => (a && (b >= c))
+a && b .. c => (a && (b .. c))
+a && b | c => (a && (b | c))
+a && b ^ c => (a && (b ^ c))
+a && b & c => (a && (b & c))
+a && b << c => (a && (b << c))
+a && b >> c => (a && (b >> c))
+a && b + c => (a && (b + c))
+a && b - c => (a && (b - c))
+a && b * c => (a && (b * c))
+a && b / c => (a && (b / c))
+a && b % c => (a && (b % c))
+a && b ~/ c => (a && (b ~/ c))
+a && b ** c => (a && (b ** c))
+a == b ?? c => ((a == b) ?? c)
+a == b || c => ((a == b) || c)
+a == b && c => ((a == b) && c)
+a == b == c => ((a == b) == c)
+a == b != c => ((a == b) != c)
+a == b < c => (a == (b < c))
+a == b <= c => (a == (b <= c))
+a == b > c => (a == (b > c))
+a == b >= c => (a == (b >= c))
+a == b .. c => (a == (b .. c))
+a == b | c => (a == (b | c))
+a == b ^ c => (a == (b ^ c))
+a == b & c => (a == (b & c))
+a == b << c => (a == (b << c))
+a == b >> c => (a == (b >> c))
+a == b + c => (a == (b + c))
+a == b - c => (a == (b - c))
+a == b * c => (a == (b * c))
+a == b / c => (a == (b / c))
+a == b % c => (a == (b % c))
+a == b ~/ c => (a == (b ~/ c))
+a == b ** c => (a == (b ** c))
+a != b ?? c => ((a != b) ?? c)
+a != b || c => ((a != b) || c)
+a != b && c => ((a != b) && c)
+a != b == c => ((a != b) == c)
+a != b != c => ((a != b) != c)
+a != b < c => (a != (b < c))
+a != b <= c => (a != (b <= c))
+a != b > c => (a != (b > c))
+a != b >= c => (a != (b >= c))
+a != b .. c => (a != (b .. c))
+a != b | c => (a != (b | c))
+a != b ^ c => (a != (b ^ c))
+a != b & c => (a != (b & c))
+a != b << c => (a != (b << c))
+a != b >> c => (a != (b >> c))
+a != b + c => (a != (b + c))
+a != b - c => (a != (b - c))
+a != b * c => (a != (b * c))
+a != b / c => (a != (b / c))
+a != b % c => (a != (b % c))
+a != b ~/ c => (a != (b ~/ c))
+a != b ** c => (a != (b ** c))
+a < b ?? c => ((a < b) ?? c)
+a < b || c => ((a < b) || c)
+a < b && c => ((a < b) && c)
+a < b == c => ((a < b) == c)
+a < b != c => ((a < b) != c)
+a < b < c => ((a < b) < c)
+a < b <= c => ((a < b) <= c)
+a < b > c => ((a < b) > c)
+a < b >= c => ((a < b) >= c)
+a < b .. c => (a < (b .. c))
+a < b | c => (a < (b | c))
+a < b ^ c => (a < (b ^ c))
+a < b & c => (a < (b & c))
+a < b << c => (a < (b << c))
+a < b >> c => (a < (b >> c))
+a < b + c => (a < (b + c))
+a < b - c => (a < (b - c))
+a < b * c => (a < (b * c))
+a < b / c => (a < (b / c))
+a < b % c => (a < (b % c))
+a < b ~/ c => (a < (b ~/ c))
+a < b ** c => (a < (b ** c))
+a <= b ?? c => ((a <= b) ?? c)
+a <= b || c => ((a <= b) || c)
+a <= b && c => ((a <= b) && c)
+a <= b == c => ((a <= b) == c)
+a <= b != c => ((a <= b) != c)
+a <= b < c => ((a <= b) < c)
+a <= b <= c => ((a <= b) <= c)
+a <= b > c => ((a <= b) > c)
+a <= b >= c => ((a <= b) >= c)
+a <= b .. c => (a <= (b .. c))
+a <= b | c => (a <= (b | c))
+a <= b ^ c => (a <= (b ^ c))
+a <= b & c => (a <= (b & c))
+a <= b << c => (a <= (b << c))
+a <= b >> c => (a <= (b >> c))
+a <= b + c => (a <= (b + c))
+a <= b - c => (a <= (b - c))
+a <= b * c => (a <= (b * c))
+a <= b / c => (a <= (b / c))
+a <= b % c => (a <= (b % c))
+a <= b ~/ c => (a <= (b ~/ c))
+a <= b ** c => (a <= (b ** c))
+a > b ?? c => ((a > b) ?? c)
+a > b || c => ((a > b) || c)
+a > b && c => ((a > b) && c)
+a > b == c => ((a > b) == c)
+a > b != c => ((a > b) != c)
+a > b < c => ((a > b) < c)
+a > b <= c => ((a > b) <= c)
+a > b > c => ((a > b) > c)
+a > b >= c => ((a > b) >= c)
+a > b .. c => (a > (b .. c))
+a > b | c => (a > (b | c))
+a > b ^ c => (a > (b ^ c))
+a > b & c => (a > (b & c))
+a > b << c => (a > (b << c))
+a > b >> c => (a > (b >> c))
+a > b + c => (a > (b + c))
+a > b - c => (a > (b - c))
+a > b * c => (a > (b * c))
+a > b / c => (a > (b / c))
+a > b % c => (a > (b % c))
+a > b ~/ c => (a > (b ~/ c))
+a > b ** c => (a > (b ** c))
+a >= b ?? c => ((a >= b) ?? c)
+a >= b || c => ((a >= b) || c)
+a >= b && c => ((a >= b) && c)
+a >= b == c => ((a >= b) == c)
+a >= b != c => ((a >= b) != c)
+a >= b < c => ((a >= b) < c)
+a >= b <= c => ((a >= b) <= c)
+a >= b > c => ((a >= b) > c)
+a >= b >= c => ((a >= b) >= c)
+a >= b .. c => (a >= (b .. c))
+a >= b | c => (a >= (b | c))
+a >= b ^ c => (a >= (b ^ c))
+a >= b & c => (a >= (b & c))
+a >= b << c => (a >= (b << c))
+a >= b >> c => (a >= (b >> c))
+a >= b + c => (a >= (b + c))
+a >= b - c => (a >= (b - c))
+a >= b * c => (a >= (b * c))
+a >= b / c => (a >= (b / c))
+a >= b % c => (a >= (b % c))
+a >= b ~/ c => (a >= (b ~/ c))
+a >= b ** c => (a >= (b ** c))
+a .. b ?? c => ((a .. b) ?? c)
+a .. b || c => ((a .. b) || c)
+a .. b && c => ((a .. b) && c)
+a .. b == c => ((a .. b) == c)
+a .. b != c => ((a .. b) != c)
+a .. b < c => ((a .. b) < c)
+a .. b <= c => ((a .. b) <= c)
+a .. b > c => ((a .. b) > c)
+a .. b >= c => ((a .. b) >= c)
+a .. b .. c => ((a .. b) .. c)
+a .. b | c => (a .. (b | c))
+a .. b ^ c => (a .. (b ^ c))
+a .. b & c => (a .. (b & c))
+a .. b << c => (a .. (b << c))
+a .. b >> c => (a .. (b >> c))
+a .. b + c => (a .. (b + c))
+a .. b - c => (a .. (b - c))
+a .. b * c => (a .. (b * c))
+a .. b / c => (a .. (b / c))
+a .. b % c => (a .. (b % c))
+a .. b ~/ c => (a .. (b ~/ c))
+a .. b ** c => (a .. (b ** c))
+a | b ?? c => ((a | b) ?? c)
+a | b || c => ((a | b) || c)
+a | b && c => ((a | b) && c)
+a | b == c => ((a | b) == c)
+a | b != c => ((a | b) != c)
+a | b < c => ((a | b) < c)
+a | b <= c => ((a | b) <= c)
+a | b > c => ((a | b) > c)
+a | b >= c => ((a | b) >= c)
+a | b .. c => ((a | b) .. c)
+a | b | c => ((a | b) | c)
+a | b ^ c => (a | (b ^ c))
+a | b & c => (a | (b & c))
+a | b << c => (a | (b << c))
+a | b >> c => (a | (b >> c))
+a | b + c => (a | (b + c))
+a | b - c => (a | (b - c))
+a | b * c => (a | (b * c))
+a | b / c => (a | (b / c))
+a | b % c => (a | (b % c))
+a | b ~/ c => (a | (b ~/ c))
+a | b ** c => (a | (b ** c))
+a ^ b ?? c => ((a ^ b) ?? c)
+a ^ b || c => ((a ^ b) || c)
+a ^ b && c => ((a ^ b) && c)
+a ^ b == c => ((a ^ b) == c)
+a ^ b != c => ((a ^ b) != c)
+a ^ b < c => ((a ^ b) < c)
+a ^ b <= c => ((a ^ b) <= c)
+a ^ b > c => ((a ^ b) > c)
+a ^ b >= c => ((a ^ b) >= c)
+a ^ b .. c => ((a ^ b) .. c)
+a ^ b | c => ((a ^ b) | c)
+a ^ b ^ c => ((a ^ b) ^ c)
+a ^ b & c => (a ^ (b & c))
+a ^ b << c => (a ^ (b << c))
+a ^ b >> c => (a ^ (b >> c))
+a ^ b + c => (a ^ (b + c))
+a ^ b - c => (a ^ (b - c))
+a ^ b * c => (a ^ (b * c))
+a ^ b / c => (a ^ (b / c))
+a ^ b % c => (a ^ (b % c))
+a ^ b ~/ c => (a ^ (b ~/ c))
+a ^ b ** c => (a ^ (b ** c))
+a & b ?? c => ((a & b) ?? c)
+a & b || c => ((a & b) || c)
+a & b && c => ((a & b) && c)
+a & b == c => ((a & b) == c)
+a & b != c => ((a & b) != c)
+a & b < c => ((a & b) < c)
+a & b <= c => ((a & b) <= c)
+a & b > c => ((a & b) > c)
+a & b >= c => ((a & b) >= c)
+a & b .. c => ((a & b) .. c)
+a & b | c => ((a & b) | c)
+a & b ^ c => ((a & b) ^ c)
+a & b & c => ((a & b) & c)
+a & b << c => (a & (b << c))
+a & b >> c => (a & (b >> c))
+a & b + c => (a & (b + c))
+a & b - c => (a & (b - c))
+a & b * c => (a & (b * c))
+a & b / c => (a & (b / c))
+a & b % c => (a & (b % c))
+a & b ~/ c => (a & (b ~/ c))
+a & b ** c => (a & (b ** c))
+a << b ?? c => ((a << b) ?? c)
+a << b || c => ((a << b) || c)
+a << b && c => ((a << b) && c)
+a << b == c => ((a << b) == c)
+a << b != c => ((a << b) != c)
+a << b < c => ((a << b) < c)
+a << b <= c => ((a << b) <= c)
+a << b > c => ((a << b) > c)
+a << b >= c => ((a << b) >= c)
+a << b .. c => ((a << b) .. c)
+a << b | c => ((a << b) | c)
+a << b ^ c => ((a << b) ^ c)
+a << b & c => ((a << b) & c)
+a << b << c => ((a << b) << c)
+a << b >> c => ((a << b) >> c)
+a << b + c => (a << (b + c))
+a << b - c => (a << (b - c))
+a << b * c => (a << (b * c))
+a << b / c => (a << (b / c))
+a << b % c => (a << (b % c))
+a << b ~/ c => (a << (b ~/ c))
+a << b ** c => (a << (b ** c))
+a >> b ?? c => ((a >> b) ?? c)
+a >> b || c => ((a >> b) || c)
+a >> b && c => ((a >> b) && c)
+a >> b == c => ((a >> b) == c)
+a >> b != c => ((a >> b) != c)
+a >> b < c => ((a >> b) < c)
+a >> b <= c => ((a >> b) <= c)
+a >> b > c => ((a >> b) > c)
+a >> b >= c => ((a >> b) >= c)
+a >> b .. c => ((a >> b) .. c)
+a >> b | c => ((a >> b) | c)
+a >> b ^ c => ((a >> b) ^ c)
+a >> b & c => ((a >> b) & c)
+a >> b << c => ((a >> b) << c)
+a >> b >> c => ((a >> b) >> c)
+a >> b + c => (a >> (b + c))
+a >> b - c => (a >> (b - c))
+a >> b * c => (a >> (b * c))
+a >> b / c => (a >> (b / c))
+a >> b % c => (a >> (b % c))
+a >> b ~/ c => (a >> (b ~/ c))
+a >> b ** c => (a >> (b ** c))
+a + b ?? c => ((a + b) ?? c)
+a + b || c => ((a + b) || c)
+a + b && c => ((a + b) && c)
+a + b == c => ((a + b) == c)
+a + b != c => ((a + b) != c)
+a + b < c => ((a + b) < c)
+a + b <= c => ((a + b) <= c)
+a + b > c => ((a + b) > c)
+a + b >= c => ((a + b) >= c)
+a + b .. c => ((a + b) .. c)
+a + b | c => ((a + b) | c)
+a + b ^ c => ((a + b) ^ c)
+a + b & c => ((a + b) & c)
+a + b << c => ((a + b) << c)
+a + b >> c => ((a + b) >> c)
+a + b + c => ((a + b) + c)
+a + b - c => ((a + b) - c)
+a + b * c => (a + (b * c))
+a + b / c => (a + (b / c))
+a + b % c => (a + (b % c))
+a + b ~/ c => (a + (b ~/ c))
+a + b ** c => (a + (b ** c))
+a - b ?? c => ((a - b) ?? c)
+a - b || c => ((a - b) || c)
+a - b && c => ((a - b) && c)
+a - b == c => ((a - b) == c)
+a - b != c => ((a - b) != c)
+a - b < c => ((a - b) < c)
+a - b <= c => ((a - b) <= c)
+a - b > c => ((a - b) > c)
+a - b >= c => ((a - b) >= c)
+a - b .. c => ((a - b) .. c)
+a - b | c => ((a - b) | c)
+a - b ^ c => ((a - b) ^ c)
+a - b & c => ((a - b) & c)
+a - b << c => ((a - b) << c)
+a - b >> c => ((a - b) >> c)
+a - b + c => ((a - b) + c)
+a - b - c => ((a - b) - c)
+a - b * c => (a - (b * c))
+a - b / c => (a - (b / c))
+a - b % c => (a - (b % c))
+a - b ~/ c => (a - (b ~/ c))
+a - b ** c => (a - (b ** c))
+a * b ?? c => ((a * b) ?? c)
+a * b || c => ((a * b) || c)
+a * b && c => ((a * b) && c)
+a * b == c => ((a * b) == c)
+a * b != c => ((a * b) != c)
+a * b < c => ((a * b) < c)
+a * b <= c => ((a * b) <= c)
+a * b > c => ((a * b) > c)
+a * b >= c => ((a * b) >= c)
+a * b .. c => ((a * b) .. c)
+a * b | c => ((a * b) | c)
+a * b ^ c => ((a * b) ^ c)
+a * b & c => ((a * b) & c)
+a * b << c => ((a * b) << c)
+a * b >> c => ((a * b) >> c)
+a * b + c => ((a * b) + c)
+a * b - c => ((a * b) - c)
+a * b * c => ((a * b) * c)
+a * b / c => ((a * b) / c)
+a * b % c => ((a * b) % c)
+a * b ~/ c => ((a * b) ~/ c)
+a * b ** c => (a * (b ** c))
+a / b ?? c => ((a / b) ?? c)
+a / b || c => ((a / b) || c)
+a / b && c => ((a / b) && c)
+a / b == c => ((a / b) == c)
+a / b != c => ((a / b) != c)
+a / b < c => ((a / b) < c)
+a / b <= c => ((a / b) <= c)
+a / b > c => ((a / b) > c)
+a / b >= c => ((a / b) >= c)
+a / b .. c => ((a / b) .. c)
+a / b | c => ((a / b) | c)
+a / b ^ c => ((a / b) ^ c)
+a / b & c => ((a / b) & c)
+a / b << c => ((a / b) << c)
+a / b >> c => ((a / b) >> c)
+a / b + c => ((a / b) + c)
+a / b - c => ((a / b) - c)
+a / b * c => ((a / b) * c)
+a / b / c => ((a / b) / c)
+a / b % c => ((a / b) % c)
+a / b ~/ c => ((a / b) ~/ c)
+a / b ** c => (a / (b ** c))
+a % b ?? c => ((a % b) ?? c)
+a % b || c => ((a % b) || c)
+a % b && c => ((a % b) && c)
+a % b == c => ((a % b) == c)
+a % b != c => ((a % b) != c)
+a % b < c => ((a % b) < c)
+a % b <= c => ((a % b) <= c)
+a % b > c => ((a % b) > c)
+a % b >= c => ((a % b) >= c)
+a % b .. c => ((a % b) .. c)
+a % b | c => ((a % b) | c)
+a % b ^ c => ((a % b) ^ c)
+a % b & c => ((a % b) & c)
+a % b << c => ((a % b) << c)
+a % b >> c => ((a % b) >> c)
+a % b + c => ((a % b) + c)
+a % b - c => ((a % b) - c)
+a % b * c => ((a % b) * c)
+a % b / c => ((a % b) / c)
+a % b % c => ((a % b) % c)
+a % b ~/ c => ((a % b) ~/ c)
+a % b ** c => (a % (b ** c))
+a ~/ b ?? c => ((a ~/ b) ?? c)
+a ~/ b || c => ((a ~/ b) || c)
+a ~/ b && c => ((a ~/ b) && c)
+a ~/ b == c => ((a ~/ b) == c)
+a ~/ b != c => ((a ~/ b) != c)
+a ~/ b < c => ((a ~/ b) < c)
+a ~/ b <= c => ((a ~/ b) <= c)
+a ~/ b > c => ((a ~/ b) > c)
+a ~/ b >= c => ((a ~/ b) >= c)
+a ~/ b .. c => ((a ~/ b) .. c)
+a ~/ b | c => ((a ~/ b) | c)
+a ~/ b ^ c => ((a ~/ b) ^ c)
+a ~/ b & c => ((a ~/ b) & c)
+a ~/ b << c => ((a ~/ b) << c)
+a ~/ b >> c => ((a ~/ b) >> c)
+a ~/ b + c => ((a ~/ b) + c)
+a ~/ b - c => ((a ~/ b) - c)
+a ~/ b * c => ((a ~/ b) * c)
+a ~/ b / c => ((a ~/ b) / c)
+a ~/ b % c => ((a ~/ b) % c)
+a ~/ b ~/ c => ((a ~/ b) ~/ c)
+a ~/ b ** c => (a ~/ (b ** c))
+a ** b ?? c => ((a ** b) ?? c)
+a ** b || c => ((a ** b) || c)
+a ** b && c => ((a ** b) && c)
+a ** b == c => ((a ** b) == c)
+a ** b != c => ((a ** b) != c)
+a ** b < c => ((a ** b) < c)
+a ** b <= c => ((a ** b) <= c)
+a ** b > c => ((a ** b) > c)
+a ** b >= c => ((a ** b) >= c)
+a ** b .. c => ((a ** b) .. c)
+a ** b | c => ((a ** b) | c)
+a ** b ^ c => ((a ** b) ^ c)
+a ** b & c => ((a ** b) & c)
+a ** b << c => ((a ** b) << c)
+a ** b >> c => ((a ** b) >> c)
+a ** b + c => ((a ** b) + c)
+a ** b - c => ((a ** b) - c)
+a ** b * c => ((a ** b) * c)
+a ** b / c => ((a ** b) / c)
+a ** b % c => ((a ** b) % c)
+a ** b ~/ c => ((a ** b) ~/ c)
+a ** b ** c => (a ** (b ** c))
+-a * b => ((-a) * b)
+!a && b => ((!a) && b)
+a * -b => (a * (-b))
+a = b || c => (a = (b || c))
+a = b = c + 1 => (a = (b = (c + 1)))
+a += b * 2 => (a += (b * 2))
+a < b + 1 => (a < (b + 1))
+a || b == c => (a || (b == c))
+0..n + 1 => (0 .. (n + 1))
+a == b < c && d => ((a == (b < c)) && d)
+-a ** b => (-(a ** b))
+~a & b => ((~a) & b)
+a ** -b => (a ** (-b))
+a & b == c => ((a & b) == c)
+1 << n - 1 => (1 << (n - 1))
+a ~/ b ** c => (a ~/ (b ** c))
+a?.b ?? c => ((a?.b) ?? c)
+a?[0]?.b.c => (((a?[0])?.b).c)
+-a?.b => (-(a?.b))
+x = a ?? b => (x = (a ?? b))
+a == null ?? b => ((a == null) ?? b)
+";
+
+#[test]
+fn will_you_respect_precedence() {
+    for case in PRECEDENCE_GOLDEN.lines() {
+        let (input, expected) = case.split_once(" => ").unwrap();
+        let lexer = Lexer::new(input.into());
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+
+        assert_eq!(check_parser_errors(&parser), 0, "{}", input);
+        let Statement::Expression(expr) = &program.statements[0] else {
+            panic!("expected an expression for {}", input);
+        };
+        assert_eq!(grouped(expr), expected, "{}", input);
+    }
+}
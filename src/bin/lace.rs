@@ -1,10 +1,13 @@
-use std::env;
+use std::{env, process};
 
 use lace::cli::InterpreterArgs;
 
 fn main() {
     let args = env::args().collect::<Vec<String>>();
 
-    let interpreter = InterpreterArgs::from(args);
+    let interpreter = InterpreterArgs::try_from(args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(2);
+    });
     interpreter.run();
 }
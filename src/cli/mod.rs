@@ -1,36 +1,49 @@
-use std::path::PathBuf;
+use std::{io, path::PathBuf};
+
+use lace_parser::diagnostic::{ColorChoice, Emitter, ErrorFormat};
 
 use crate::{interpretter, repl};
 
 #[derive(Default)]
 pub struct InterpreterArgs {
     file: Option<PathBuf>,
+    color: ColorChoice,
+    error_format: ErrorFormat,
 }
 
-impl From<Vec<String>> for InterpreterArgs {
-    fn from(value: Vec<String>) -> Self {
-        let len = value.len();
-        if len <= 1 {
-            Self::default()
-        } else if len > 2 {
-            panic!("Too many arguments received")
-        } else {
-            Self {
-                file: Some(PathBuf::from(&value[1])),
+impl TryFrom<Vec<String>> for InterpreterArgs {
+    type Error = String;
+
+    fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
+        let mut args = Self::default();
+
+        for arg in value.iter().skip(1) {
+            if let Some(choice) = arg.strip_prefix("--color=") {
+                args.color = choice.parse()?;
+            } else if let Some(format) = arg.strip_prefix("--error-format=") {
+                args.error_format = format.parse()?;
+            } else if args.file.is_none() {
+                args.file = Some(PathBuf::from(arg));
+            } else {
+                return Err("Too many arguments received".into());
             }
         }
+
+        Ok(args)
     }
 }
 
 impl InterpreterArgs {
     pub fn run(&self) {
+        let emitter = Emitter::new(self.color, self.error_format, &io::stderr());
+
         match &self.file {
             Some(file_path) => {
-                if let Err(err) = interpretter::run_interpreter(file_path.clone()) {
+                if let Err(err) = interpretter::run_interpreter(file_path.clone(), emitter) {
                     eprintln!("{err}");
                 }
             }
-            None => repl::run_repl(),
+            None => repl::run_repl(emitter),
         }
     }
 }
@@ -1,8 +1,12 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
 
-use lace_eval::Eval;
+use lace_eval::{object::Object, Eval};
 use lace_lexer::Lexer;
-use lace_parser::Parser;
+use lace_parser::{diagnostic::Emitter, Parser};
 
 macro_rules! check_ext {
     ($source:expr) => {
@@ -17,7 +21,7 @@ macro_rules! check_ext {
         }
     };
 }
-pub fn run_interpreter(source: PathBuf) -> Result<(), String> {
+pub fn run_interpreter(source: PathBuf, emitter: Emitter) -> Result<(), String> {
     if !source.is_file() {
         return Err(format!("Couldn't find {:?}", source));
     }
@@ -31,16 +35,27 @@ pub fn run_interpreter(source: PathBuf) -> Result<(), String> {
         }
     };
 
-    let lexer = Lexer::new(code);
-    let mut parser = Parser::new(lexer);
+    // the evaluator reports runtime errors against the canonical path,
+    // so parse errors use it too
+    let source = source.canonicalize().unwrap_or(source);
+    let lexer = Lexer::new(code.clone());
+    let mut parser = Parser::for_file(lexer, source.clone());
     let program = parser.parse_program();
 
-    if parser.errors.is_empty() {
-        let mut evaluator = Eval::new();
-        evaluator.eval(program);
+    let mut stderr = io::stderr();
+    let written = if parser.errors.is_empty() {
+        let mut evaluator = Eval::for_file(source);
+        evaluator.loader.emitter = emitter;
+
+        match evaluator.eval(program) {
+            Object::Error(err) => err.emit(&mut stderr, &code, &emitter),
+            _ => Ok(()),
+        }
     } else {
-        parser.log_errors();
-    }
+        parser.emit_errors(&mut stderr, &emitter)
+    };
 
-    Ok(())
+    written
+        .and_then(|_| stderr.flush())
+        .map_err(|err| format!("Failed to report errors: {}", err))
 }
@@ -7,12 +7,12 @@ use std::{
 
 use lace_eval::{object::Object, Eval};
 use lace_lexer::Lexer;
-use lace_parser::Parser;
+use lace_parser::{diagnostic::Emitter, Parser};
 
 use self::prompt::ReplPrompt;
 
-pub fn run_repl() {
-    let (stdin, mut stdout, stderr): (Stdin, Stdout, Stderr) =
+pub fn run_repl(emitter: Emitter) {
+    let (stdin, mut stdout, mut stderr): (Stdin, Stdout, Stderr) =
         (io::stdin(), io::stdout(), io::stderr());
 
     let mut prompt = ReplPrompt::default();
@@ -20,6 +20,7 @@ pub fn run_repl() {
     write!(&stdout, "{}", prompt.logo).unwrap();
 
     let mut eval = Eval::default();
+    eval.loader.emitter = emitter;
 
     loop {
         write!(&stdout, "{}{}\x1b[0m", prompt.colour(), prompt.symbol).unwrap();
@@ -33,7 +34,7 @@ pub fn run_repl() {
             process::exit(1);
         };
 
-        let lexer = Lexer::new(input);
+        let lexer = Lexer::new(input.clone());
         let mut parser = Parser::new(lexer);
 
         let program = parser.parse_program();
@@ -43,7 +44,7 @@ pub fn run_repl() {
                 prompt.works();
                 let v = eval.eval(program);
                 if let Object::Error(err) = v {
-                    writeln!(&stderr, "{}", err).unwrap();
+                    err.emit(&mut stderr, &input, &emitter).unwrap();
                     prompt.errored();
                 } else if let Object::Null = v {
                     // let the prompt remain empty if there is expression is evaluated to null
@@ -53,9 +54,7 @@ pub fn run_repl() {
             }
             false => {
                 prompt.errored();
-                parser.errors.iter().for_each(|e| {
-                    println!("{}", e.emit_err());
-                })
+                parser.emit_errors(&mut stderr, &emitter).unwrap();
             }
         }
     }